@@ -19,7 +19,9 @@ pub use self::block::BlockHead;
 use crate::{
     error::Error,
     evm::opcodes::{gen_associated_ops, gen_associated_steps},
-    operation::{self, CallContextField, Operation, RWCounter, StartOp, StorageOp, RW},
+    operation::{
+        self, CallContextField, Operation, RWCounter, RwDiff, RwRow, StartOp, StorageOp, RW,
+    },
 };
 pub use access::{Access, AccessSet, AccessValue, CodeSource};
 pub use block::{Block, BlockContext};
@@ -31,19 +33,24 @@ use eth_types::{
     evm_types::{GasCost, OpcodeId},
     sign_types::get_dummy_tx,
     state_db::{CodeDB, StateDB},
-    EthBlock, GethExecTrace, Word, H256,
+    EthBlock, GethExecStep, GethExecTrace, Word, H256,
 };
 use ethers_core::utils::keccak256;
 pub use execution::{
-    BigModExp, CopyAccessList, CopyBytes, CopyDataType, CopyEvent, CopyEventStepsBuilder, CopyStep,
-    EcAddOp, EcMulOp, EcPairingOp, EcPairingPair, ExecState, ExecStep, ExpEvent, ExpStep,
-    NumberOrHash, PrecompileEvent, PrecompileEvents, N_BYTES_PER_PAIR, N_PAIRING_PER_OP, SHA256,
+    BigModExp, Blake2fOp, CopyAccessList, CopyBytes, CopyDataType, CopyEvent,
+    CopyEventStepsBuilder, CopyRwOverlap, CopyStep, EcAddOp, EcMulOp, EcPairingOp, EcPairingPair,
+    ExecState, ExecStep, ExpEvent, ExpStep, NumberOrHash, PrecompileEvent, PrecompileEvents,
+    TraceViolation, TraceViolationKind, N_BYTES_PER_PAIR, N_PAIRING_PER_OP, SHA256,
 };
 pub use input_state_ref::CircuitInputStateRef;
 use itertools::Itertools;
 #[cfg(feature = "scroll")]
 use mpt_zktrie::state::ZktrieState;
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Range,
+    time::{Duration, Instant},
+};
 pub use transaction::{
     Transaction, TransactionContext, TxL1Fee, TX_L1_COMMIT_EXTRA_COST, TX_L1_FEE_PRECISION,
 };
@@ -69,6 +76,32 @@ impl Default for PrecompileEcParams {
     }
 }
 
+/// Block numbers activating the Scroll hardforks of a chain, so that a chunk
+/// can contain the block upgrading the L1 gas price oracle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChainSpec {
+    /// First block of the Curie hardfork, if it activates on the chain.
+    pub curie_block: Option<u64>,
+}
+
+impl ChainSpec {
+    /// Hardfork activations known for a chain id.
+    pub fn for_chain(chain_id: u64) -> Self {
+        match chain_id {
+            222222 => Self {
+                curie_block: Some(5),
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Whether the block is the one activating Curie, at the end of which the
+    /// L1 gas price oracle is upgraded.
+    pub fn is_curie_fork(&self, block_num: u64) -> bool {
+        self.curie_block == Some(block_num)
+    }
+}
+
 /// Circuit Setup Parameters
 #[derive(Debug, Clone, Copy)]
 pub struct CircuitsParams {
@@ -83,6 +116,8 @@ pub struct CircuitsParams {
     /// Maximum number of rows that the RLP Circuit can have
     pub max_rlp_rows: usize,
     /// Max amount of rows that the CopyCircuit can have.
+    /// When 0, it is set to the rows required by the copy events rounded up
+    /// to the next power of two when converting the witness block.
     pub max_copy_rows: usize,
     /// Maximum number of inner blocks in a chunk
     pub max_inner_blocks: usize,
@@ -93,10 +128,10 @@ pub struct CircuitsParams {
     /// Maximum number of bytes supported in the Bytecode Circuit
     pub max_bytecode: usize,
     /// Pad evm circuit number of rows.
-    /// When 0, the EVM circuit number of rows will be dynamically calculated,
-    /// so the same circuit will not be able to proof different witnesses.
-    /// In this case it will contain as many rows for all steps + 1 row
-    /// for EndBlock.
+    /// When 0, it is set to the rows required by all steps, the EndBlock and
+    /// the unused rows, rounded up to the next power of two when converting
+    /// the witness block, so that blocks of similar sizes share the same
+    /// circuit.
     pub max_evm_rows: usize,
     /// Max amount of rows that the MptCircuit can have.
     pub max_mpt_rows: usize,
@@ -115,6 +150,17 @@ pub struct CircuitsParams {
     /// then if there is 1 ecPairing in the input, we will return 500_000 as the "row usage"
     /// for the ec circuit.
     pub max_vertical_circuit_rows: usize,
+    /// Hardfork activations, see [`Self::chain_spec`]. When `None` the ones
+    /// known for the chain id are used.
+    pub chain_spec: Option<ChainSpec>,
+}
+
+impl CircuitsParams {
+    /// Hardfork activations of the chain.
+    pub fn chain_spec(&self, chain_id: u64) -> ChainSpec {
+        self.chain_spec
+            .unwrap_or_else(|| ChainSpec::for_chain(chain_id))
+    }
 }
 
 impl Default for CircuitsParams {
@@ -137,10 +183,54 @@ impl Default for CircuitsParams {
             max_vertical_circuit_rows: 0,
             max_rlp_rows: 1000,
             max_ec_ops: PrecompileEcParams::default(),
+            chain_spec: None,
         }
     }
 }
 
+/// Rows taken by the events in the sub-circuits, which depend on the layout
+/// of the circuits and are provided by them.
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitRowHeights {
+    /// EVM circuit rows of an execution step.
+    pub evm_step: fn(&ExecStep) -> usize,
+    /// EVM circuit rows of the EndInnerBlock step closing a block.
+    pub evm_end_inner_block: usize,
+    /// EVM circuit rows following the last step, including EndBlock.
+    pub evm_end_block: usize,
+    /// Copy circuit rows which are not assigned any copied byte.
+    pub copy_unused: usize,
+    /// Keccak circuit rows of one keccak_f permutation.
+    pub keccak_f: usize,
+    /// Keccak circuit rows which are not assigned any permutation.
+    pub keccak_unused: usize,
+    /// Exp circuit rows of one exponentiation step.
+    pub exp_step: usize,
+    /// Exp circuit rows which are not assigned any step.
+    pub exp_unused: usize,
+}
+
+/// Projected rows of the sub-circuits for the events collected by a
+/// [`CircuitInputBuilder`], obtained without converting them into a witness.
+/// The rows do not include the padding up to the configured circuit sizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CircuitCapacity {
+    /// Rows of the EVM circuit.
+    pub evm: usize,
+    /// Rows of the state circuit: one per rw operation plus the Start row.
+    pub state: usize,
+    /// Rows of the copy circuit.
+    pub copy: usize,
+    /// Rows of the keccak circuit for the SHA3 inputs and the transaction
+    /// hashes. The fixed number of inputs added by the PI circuit, the dummy
+    /// transaction and the ecrecover calls are not counted.
+    pub keccak: usize,
+    /// Rows of the bytecode circuit: one per byte plus one per bytecode.
+    pub bytecode: usize,
+    /// Rows of the exp circuit.
+    pub exp: usize,
+}
+
 /// Builder to generate a complete circuit input from data gathered from a geth
 /// instance. This structure is the centre of the crate and is intended to be
 /// the only entry point to it. The `CircuitInputBuilder` works in several
@@ -172,6 +262,9 @@ pub struct CircuitInputBuilder {
     #[cfg(feature = "scroll")]
     /// Initial Zktrie Status for a incremental updating
     pub mpt_init_state: Option<ZktrieState>,
+    /// Accumulated time spent in `gen_associated_ops` per opcode, only
+    /// collected when profiling is enabled.
+    opcode_timings: Option<HashMap<OpcodeId, Duration>>,
 }
 
 impl<'a> CircuitInputBuilder {
@@ -185,8 +278,39 @@ impl<'a> CircuitInputBuilder {
             block_ctx: BlockContext::new(),
             #[cfg(feature = "scroll")]
             mpt_init_state: Default::default(),
+            opcode_timings: None,
         }
     }
+
+    /// Enable or disable recording of the time spent handling each opcode.
+    pub fn with_profile(mut self, profile: bool) -> Self {
+        self.opcode_timings = profile.then(HashMap::new);
+        self
+    }
+
+    /// Take the memory written by the copy opcodes and RETURN/REVERT from
+    /// the memory captured in the traces (`enableMemory`) instead of
+    /// reconstructing it byte by byte. All the traces handled by the builder
+    /// must carry the memory.
+    #[cfg(feature = "enable-memory")]
+    pub fn with_trace_memory(mut self, trace_memory: bool) -> Self {
+        self.block_ctx.trace_memory = trace_memory;
+        self
+    }
+
+    /// Still reconstruct the memory taken from the traces and check it
+    /// against them, which is what debug builds do by default.
+    #[cfg(feature = "enable-memory")]
+    pub fn with_trace_memory_check(mut self, check: bool) -> Self {
+        self.block_ctx.check_trace_memory = check;
+        self
+    }
+
+    /// Total time spent in `gen_associated_ops` per opcode. Empty unless
+    /// profiling was enabled with [`Self::with_profile`].
+    pub fn opcode_timings(&self) -> HashMap<OpcodeId, Duration> {
+        self.opcode_timings.clone().unwrap_or_default()
+    }
     /// Create a new CircuitInputBuilder from the given `eth_block` and
     /// `constants`.
     pub fn new_from_headers(
@@ -272,6 +396,34 @@ impl<'a> CircuitInputBuilder {
     ) -> Result<(), Error> {
         self.handle_block_inner(eth_block, geth_traces, true, true)
     }
+    /// Handle only the transactions of a block whose indices fall in `range`,
+    /// e.g. to isolate a problematic transaction.
+    ///
+    /// The transactions before `range.start` are not replayed: the caller must
+    /// seed `sdb` and `code_db` with the state they leave behind, for instance
+    /// from the prestate trace of the transaction at `range.start`. Otherwise
+    /// a transaction reading state written by a skipped one diverges from its
+    /// trace.
+    pub fn handle_block_range(
+        &mut self,
+        eth_block: &EthBlock,
+        geth_traces: &[eth_types::GethExecTrace],
+        range: Range<usize>,
+    ) -> Result<(), Error> {
+        if eth_block.transactions.len() != geth_traces.len() {
+            return Err(Error::InternalError(
+                "number of block transactions and traces differ",
+            ));
+        }
+        if range.start > range.end || range.end > geth_traces.len() {
+            return Err(Error::InternalError(
+                "transaction range out of the block bounds",
+            ));
+        }
+        let mut eth_block = eth_block.clone();
+        eth_block.transactions = eth_block.transactions[range.clone()].to_vec();
+        self.handle_block_inner(&eth_block, &geth_traces[range], true, true)
+    }
     /// Handle a block by handling each transaction to generate all the
     /// associated operations.
     pub fn handle_block_inner(
@@ -281,6 +433,8 @@ impl<'a> CircuitInputBuilder {
         is_last_block: bool,
         check_last_tx: bool,
     ) -> Result<(), Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("handle_block", number = ?eth_block.number).entered();
         // accumulates gas across all txs in the block
         log::info!(
             "handling block {:?}, tx num {}",
@@ -387,6 +541,235 @@ impl<'a> CircuitInputBuilder {
         Ok(())
     }
 
+    /// Compare the RW table of the built block against a reference one and
+    /// return the first mismatching row, together with the rows of the built
+    /// table around it. Returns `None` when both tables are identical.
+    pub fn diff_rw_table(&self, golden: &[RwRow]) -> Option<RwDiff> {
+        const CONTEXT_ROWS: usize = 3;
+
+        let rows = self.block.container.rw_rows();
+        let index =
+            (0..rows.len().max(golden.len())).find(|&idx| rows.get(idx) != golden.get(idx))?;
+
+        let context_start = index.saturating_sub(CONTEXT_ROWS);
+        let context_end = (index + CONTEXT_ROWS + 1).min(rows.len());
+
+        Some(RwDiff {
+            index,
+            expected: golden.get(index).cloned(),
+            actual: rows.get(index).cloned(),
+            context: rows[context_start..context_end].to_vec(),
+        })
+    }
+
+    /// Check that the RW counters consumed by every copy event map onto
+    /// operations of the RW table and are not claimed by another copy event.
+    pub fn validate_copy_rw_continuity(&self) -> Result<(), Vec<CopyRwOverlap>> {
+        let rw_counters: HashSet<u64> = self
+            .block
+            .container
+            .rw_rows()
+            .iter()
+            .map(|row| row.rwc.0 as u64)
+            .collect();
+
+        let mut overlaps = vec![];
+        let mut prev: Option<(usize, u64)> = None;
+        for (event, copy_event) in self
+            .block
+            .copy_events
+            .iter()
+            .enumerate()
+            .filter(|(_, copy_event)| copy_event.rw_counter_delta() > 0)
+            .sorted_by_key(|(_, copy_event)| copy_event.rw_counter_start())
+        {
+            let start = copy_event.rw_counter_start();
+            let rw_counters_range = start..start + copy_event.rw_counter_delta();
+            let overlaps_with = prev
+                .filter(|&(_, prev_end)| prev_end > start)
+                .map(|(prev_event, _)| prev_event);
+            let missing = rw_counters_range
+                .clone()
+                .filter(|rwc| !rw_counters.contains(rwc))
+                .collect_vec();
+            if overlaps_with.is_some() || !missing.is_empty() {
+                overlaps.push(CopyRwOverlap {
+                    event,
+                    rw_counters: rw_counters_range.clone(),
+                    overlaps_with,
+                    missing,
+                });
+            }
+            if prev.map_or(true, |(_, prev_end)| rw_counters_range.end > prev_end) {
+                prev = Some((event, rw_counters_range.end));
+            }
+        }
+
+        if overlaps.is_empty() {
+            Ok(())
+        } else {
+            Err(overlaps)
+        }
+    }
+
+    /// Check invariants of a geth trace that the opcode handlers otherwise
+    /// assert while building the witness, so that a malformed trace is
+    /// reported with all its violations before calling
+    /// [`Self::handle_block`]. The stack size and return data checks need the
+    /// `enable-stack` feature.
+    pub fn validate_trace(geth_trace: &GethExecTrace) -> Result<(), Vec<TraceViolation>> {
+        let steps = &geth_trace.struct_logs;
+        let mut violations = vec![];
+        let mut violation = |step, kind| violations.push(TraceViolation { step, kind });
+
+        if let Some(first) = steps.first().filter(|first| first.depth != 1) {
+            violation(0, TraceViolationKind::InitialDepth(first.depth));
+        }
+
+        // For each call of the current call stack, whether it is a creation
+        // and the length of the return data left by its last callee, `None`
+        // when the trace does not tell it.
+        let mut calls: Vec<(bool, Option<u64>)> = vec![(false, Some(0))];
+        for (index, step) in steps.iter().enumerate() {
+            #[cfg(feature = "enable-stack")]
+            if step.error.is_none() {
+                let (min_stack_ptr, max_stack_ptr) = step.op.valid_stack_ptr_range();
+                let size = step.stack.0.len();
+                let stack_ptr = 1024u32.checked_sub(size as u32);
+                if !stack_ptr.map_or(false, |ptr| (min_stack_ptr..=max_stack_ptr).contains(&ptr)) {
+                    violation(index, TraceViolationKind::StackSize { op: step.op, size });
+                }
+            }
+            if step.op == OpcodeId::RETURNDATACOPY && step.error.is_none() {
+                // offset + length > len(return data) fails the step, as
+                // checked by `gen_returndatacopy_step`
+                let return_data_len = calls.last().and_then(|(_, len)| *len);
+                if let (Some(return_data_len), Some(offset), Some(length)) = (
+                    return_data_len,
+                    stack_nth_last(step, 1),
+                    stack_nth_last(step, 2),
+                ) {
+                    if offset
+                        .checked_add(length)
+                        .map_or(true, |end| end > return_data_len.into())
+                    {
+                        violation(
+                            index,
+                            TraceViolationKind::ReturnDataOutOfBounds {
+                                offset,
+                                length,
+                                return_data_len,
+                            },
+                        );
+                    }
+                }
+            }
+
+            let Some(next_step) = steps.get(index + 1) else {
+                break;
+            };
+            match next_step.depth as i32 - step.depth as i32 {
+                0 => {
+                    if next_step.gas.0 > step.gas.0 {
+                        violation(
+                            index,
+                            TraceViolationKind::GasIncrease {
+                                gas: step.gas.0,
+                                next_gas: next_step.gas.0,
+                            },
+                        );
+                    }
+                    // A call to a precompile or an account without code
+                    // returns without any step in the callee.
+                    if step.op.is_call_or_create() {
+                        if let Some((_, len)) = calls.last_mut() {
+                            *len = None;
+                        }
+                    }
+                }
+                1 if step.op.is_call_or_create() => calls.push((step.op.is_create(), Some(0))),
+                -1 => {
+                    let is_create = calls.pop().map_or(false, |(is_create, _)| is_create);
+                    if let Some((_, len)) = calls.last_mut() {
+                        *len = returned_data_len(step, is_create);
+                    }
+                }
+                _ => violation(
+                    index,
+                    TraceViolationKind::DepthTransition {
+                        depth: step.depth,
+                        next_depth: next_step.depth,
+                    },
+                ),
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Estimate the sub-circuit rows required by the events collected so
+    /// far, so that block boundaries can be decided before witness
+    /// generation.
+    pub fn estimate_capacity(&self, heights: &CircuitRowHeights) -> CircuitCapacity {
+        // keccak_f absorbs 136 bytes at a time
+        const KECCAK_RATE: usize = 136;
+
+        let txs = &self.block.txs;
+        let last_block_num = self.block.headers.keys().next_back().copied();
+        // EndInnerBlock steps close the blocks from the one of the first tx
+        let num_end_inner_blocks = txs
+            .first()
+            .zip(last_block_num)
+            .map_or(0, |(tx, last)| (last + 1 - tx.block_num) as usize);
+        let evm = txs
+            .iter()
+            .flat_map(|tx| tx.steps())
+            .map(heights.evm_step)
+            .sum::<usize>()
+            + num_end_inner_blocks * heights.evm_end_inner_block
+            + heights.evm_end_block;
+
+        let chunk_tx_bytes = txs
+            .iter()
+            .filter(|tx| !tx.tx_type.is_l1_msg())
+            .flat_map(|tx| tx.rlp_bytes.iter().copied())
+            .collect::<Vec<_>>();
+        let keccak_f = txs
+            .iter()
+            .map(|tx| {
+                if tx.tx_type.is_l1_msg() {
+                    &tx.rlp_bytes
+                } else {
+                    &tx.rlp_unsigned_bytes
+                }
+            })
+            .chain(std::iter::once(&chunk_tx_bytes))
+            .chain(self.block.sha3_inputs.iter())
+            .unique()
+            .map(|input| (input.len() + KECCAK_RATE - 1) / KECCAK_RATE)
+            .sum::<usize>();
+
+        let container = &self.block.container;
+        CircuitCapacity {
+            evm,
+            state: container.rw_rows().len() + container.start.len() + 1,
+            copy: self.block.copy_event_total_len() * 2 + heights.copy_unused,
+            keccak: keccak_f * heights.keccak_f + heights.keccak_unused,
+            bytecode: self.code_db.0.values().map(|code| code.len() + 1).sum(),
+            exp: self
+                .block
+                .exp_events
+                .iter()
+                .map(|event| event.steps.len() * heights.exp_step)
+                .sum::<usize>()
+                + heights.exp_unused,
+        }
+    }
+
     fn print_rw_usage(&self) {
         // opcode -> (count, mem_rw_len, stack_rw_len)
         let mut opcode_info_map = BTreeMap::new();
@@ -444,6 +827,10 @@ impl<'a> CircuitInputBuilder {
         );
         log::debug!("tx_receipt num: {}", self.block.container.tx_receipt.len());
         log::debug!("tx_log num: {}", self.block.container.tx_log.len());
+        log::debug!(
+            "tx_created_account num: {}",
+            self.block.container.tx_created_account.len()
+        );
         log::debug!("start num: {}", self.block.container.start.len());
     }
 
@@ -503,9 +890,16 @@ impl<'a> CircuitInputBuilder {
             .unwrap_or_default();
 
         // Curie sys contract upgrade
-        let is_curie_fork_block =
-            curie::is_curie_fork(state.block.chain_id, last_block_num.as_u64());
+        let is_curie_fork_block = state
+            .block
+            .circuits_params
+            .chain_spec(state.block.chain_id)
+            .is_curie_fork(last_block_num.as_u64());
         if is_curie_fork_block {
+            log::info!(
+                "enable curie fork: chain id {} block {last_block_num}",
+                state.block.chain_id
+            );
             curie::apply_curie(&mut state, &mut end_block_step)?;
         }
 
@@ -557,6 +951,8 @@ impl<'a> CircuitInputBuilder {
         geth_trace: &GethExecTrace,
         is_last_tx: bool,
     ) -> Result<(), Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("handle_tx", hash = ?eth_tx.hash).entered();
         let mut tx = self.new_tx(eth_tx, !geth_trace.failed)?;
 
         // Sanity check for transaction L1 fee.
@@ -608,6 +1004,7 @@ impl<'a> CircuitInputBuilder {
 
         tx.steps_mut().extend(begin_tx_steps);
 
+        let profile = self.opcode_timings.is_some();
         for (index, geth_step) in geth_trace.struct_logs.iter().enumerate() {
             let tx_gas = tx.gas;
             let mut state_ref = self.state_ref(&mut tx, &mut tx_ctx);
@@ -691,11 +1088,17 @@ impl<'a> CircuitInputBuilder {
                 state_ref.call(),
                 state_ref.tx.calls()
             );
+            let start = profile.then(Instant::now);
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!("gen_associated_ops", op = ?geth_step.op).entered();
             let exec_steps = gen_associated_ops(
                 &geth_step.op,
                 &mut state_ref,
                 &geth_trace.struct_logs[index..],
             )?;
+            if let (Some(start), Some(timings)) = (start, self.opcode_timings.as_mut()) {
+                *timings.entry(geth_step.op).or_default() += start.elapsed();
+            }
             tx.steps_mut().extend(exec_steps);
         }
 
@@ -766,6 +1169,33 @@ pub fn get_create_init_code(call_ctx: &CallContext) -> Result<Vec<u8>, Error> {
     Ok(result)
 }
 
+/// Stack item of a trace step, only known with the `enable-stack` feature.
+fn stack_nth_last(step: &GethExecStep, nth: usize) -> Option<Word> {
+    #[cfg(feature = "enable-stack")]
+    {
+        step.stack.nth_last(nth).ok()
+    }
+    #[cfg(not(feature = "enable-stack"))]
+    {
+        let _ = (step, nth);
+        None
+    }
+}
+
+/// Length of the return data left to the caller by the last step of a
+/// callee. Only RETURN from a call and REVERT executed without error leave
+/// return data.
+fn returned_data_len(last_step: &GethExecStep, is_create: bool) -> Option<u64> {
+    match last_step.op {
+        OpcodeId::RETURN | OpcodeId::REVERT
+            if last_step.error.is_none() && !(is_create && last_step.op == OpcodeId::RETURN) =>
+        {
+            stack_nth_last(last_step, 1).map(|length| length.low_u64())
+        }
+        _ => Some(0),
+    }
+}
+
 /// Retrieve the memory offset and length of call.
 pub fn get_call_memory_offset_length(
     call_ctx: &CallContext,
@@ -779,3 +1209,171 @@ pub fn get_call_memory_offset_length(
         Ok((offset.low_u64(), length.low_u64()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ChainSpec, CircuitsParams};
+    use crate::mock::BlockData;
+    #[cfg(feature = "enable-memory")]
+    use crate::{circuit_input_builder::ExecState, evm::OpcodeId};
+    use eth_types::{bytecode, geth_types::GethData, Word};
+    use mock::{eth, TestContext};
+
+    #[test]
+    fn handle_block_range_only_handles_selected_txs() {
+        let code = bytecode! {
+            PUSH1(0x02)
+            PUSH1(0x00)
+            SSTORE
+            STOP
+        };
+        let block: GethData = TestContext::<4, 2>::new(
+            None,
+            |accs| {
+                accs[0].address(mock::MOCK_ACCOUNTS[0]).code(code);
+                accs[1].address(mock::MOCK_ACCOUNTS[1]).balance(eth(10));
+                accs[2].address(mock::MOCK_ACCOUNTS[2]).balance(eth(10));
+                accs[3].address(mock::MOCK_ACCOUNTS[3]);
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[1].address)
+                    .to(accs[3].address)
+                    .value(eth(1));
+                txs[1].from(accs[2].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block_range(&block.eth_block, &block.geth_traces, 1..2)
+            .unwrap();
+
+        assert_eq!(builder.block.txs().len(), 1);
+        assert_eq!(
+            builder.block.txs()[0].from,
+            block.eth_block.transactions[1].from
+        );
+        assert_eq!(
+            *builder
+                .sdb
+                .get_storage(&mock::MOCK_ACCOUNTS[0], &Word::zero())
+                .1,
+            Word::from(2)
+        );
+        // the value transfer of the skipped first tx is not applied
+        assert_eq!(
+            builder.sdb.get_balance(&mock::MOCK_ACCOUNTS[3]),
+            Word::zero()
+        );
+    }
+
+    #[test]
+    fn handle_block_range_out_of_bounds() {
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode! {
+            STOP
+        })
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        assert!(builder
+            .handle_block_range(&block.eth_block, &block.geth_traces, 1..2)
+            .is_err());
+        assert!(builder
+            .handle_block_range(&block.eth_block, &block.geth_traces[..0], 0..1)
+            .is_err());
+        assert!(builder.block.txs().is_empty());
+    }
+
+    #[test]
+    fn chain_spec_overrides_known_activations() {
+        let params = CircuitsParams::default();
+        assert!(params.chain_spec(222222).is_curie_fork(5));
+        assert_eq!(params.chain_spec(534352), ChainSpec::default());
+
+        let spec = ChainSpec {
+            curie_block: Some(10),
+        };
+        let params = CircuitsParams {
+            chain_spec: Some(spec),
+            ..Default::default()
+        };
+        assert_eq!(params.chain_spec(222222), spec);
+        assert!(!spec.is_curie_fork(5));
+        assert!(spec.is_curie_fork(10));
+    }
+
+    /// Builds a block whose memory after CODECOPY is tampered in the trace,
+    /// so that it can only be seen by the MSTORE overwriting it if it was
+    /// taken from the trace.
+    #[cfg(feature = "enable-memory")]
+    fn tampered_copy_memory_block() -> GethData {
+        let code = bytecode! {
+            PUSH1(0x20) // length
+            PUSH1(0x00) // offset
+            PUSH1(0x20) // dest offset
+            CODECOPY
+            PUSH1(0x00)
+            PUSH1(0x20)
+            MSTORE
+            STOP
+        };
+        let mut block: GethData = TestContext::<2, 1>::new_with_logger_config(
+            None,
+            mock::test_ctx::helpers::account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+            mock::test_ctx::LoggerConfig::enable_memory(),
+        )
+        .unwrap()
+        .into();
+        let struct_logs = &mut block.geth_traces[0].struct_logs;
+        let codecopy = struct_logs
+            .iter()
+            .position(|step| step.op == OpcodeId::CODECOPY)
+            .unwrap();
+        struct_logs[codecopy + 1].memory.0[0x20..0x40].fill(0xff);
+        block
+    }
+
+    #[cfg(feature = "enable-memory")]
+    #[test]
+    fn handle_block_takes_copied_memory_from_trace() {
+        let block = tampered_copy_memory_block();
+        let mut builder = BlockData::new_from_geth_data(block.clone())
+            .new_circuit_input_builder()
+            .with_trace_memory(true)
+            .with_trace_memory_check(false);
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let mstore = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::MSTORE))
+            .unwrap();
+        let memory_op = &builder.block.container.memory[mstore.bus_mapping_instance[2].as_usize()];
+        assert_eq!(memory_op.op().value_prev(), Word::MAX);
+    }
+
+    #[cfg(feature = "enable-memory")]
+    #[should_panic(expected = "reconstructed memory differs from the trace")]
+    #[test]
+    fn handle_block_checks_copied_memory_against_trace() {
+        let block = tampered_copy_memory_block();
+        let mut builder = BlockData::new_from_geth_data(block.clone())
+            .new_circuit_input_builder()
+            .with_trace_memory(true)
+            .with_trace_memory_check(true);
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+    }
+}
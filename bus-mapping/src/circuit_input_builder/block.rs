@@ -9,7 +9,7 @@ use crate::{
     operation::{OperationContainer, RWCounter},
     Error,
 };
-use eth_types::{Address, Hash, ToWord, Word};
+use eth_types::{evm_types::block_utils::block_blob_base_fee, Address, Hash, ToWord, Word};
 use std::collections::{BTreeMap, HashMap};
 
 /// Context of a [`Block`] which can mutate in a [`Transaction`].
@@ -24,6 +24,14 @@ pub struct BlockContext {
     pub(crate) call_map: HashMap<usize, (usize, usize)>,
     /// Total gas used by previous transactions in this block.
     pub(crate) cumulative_gas_used: u64,
+    /// Take the memory written by copies from the trace, see
+    /// [`CircuitInputBuilder::with_trace_memory`](super::CircuitInputBuilder::with_trace_memory).
+    #[cfg(feature = "enable-memory")]
+    pub(crate) trace_memory: bool,
+    /// Reconstruct the memory taken from the trace and check it instead, see
+    /// [`with_trace_memory_check`](super::CircuitInputBuilder::with_trace_memory_check).
+    #[cfg(feature = "enable-memory")]
+    pub(crate) check_trace_memory: bool,
 }
 
 impl Default for BlockContext {
@@ -39,6 +47,10 @@ impl BlockContext {
             rwc: RWCounter::new(),
             call_map: HashMap::new(),
             cumulative_gas_used: 0,
+            #[cfg(feature = "enable-memory")]
+            trace_memory: false,
+            #[cfg(feature = "enable-memory")]
+            check_trace_memory: cfg!(debug_assertions),
         }
     }
 }
@@ -88,6 +100,8 @@ pub struct BlockHead {
     pub difficulty: Word,
     /// base fee
     pub base_fee: Word,
+    /// blob base fee
+    pub blob_base_fee: Word,
     /// start l1 queue index
     pub start_l1_queue_index: u64,
     /// Original block from geth
@@ -131,6 +145,7 @@ impl BlockHead {
                 eth_block.difficulty
             },
             base_fee: eth_block.base_fee_per_gas.unwrap_or_default(),
+            blob_base_fee: block_blob_base_fee(eth_block),
             eth_block: eth_block.clone(),
         })
     }
@@ -173,6 +188,7 @@ impl BlockHead {
                 eth_block.difficulty
             },
             base_fee: eth_block.base_fee_per_gas.unwrap_or_default(),
+            blob_base_fee: block_blob_base_fee(eth_block),
             eth_block: eth_block.clone(),
         })
     }
@@ -214,6 +230,8 @@ pub struct Block {
     pub precompile_events: PrecompileEvents,
     /// circuit capacity counter
     copy_counter: usize,
+    /// Number of EXP opcodes sharing each (base, exponent) of the exponentiation events
+    exp_event_multiplicities: HashMap<(Word, Word), usize>,
     /// relax mode indicate builder and circuit would skip
     /// some sanity check, used by testing and debugging
     relax_mode: bool,
@@ -327,15 +345,32 @@ impl Block {
             panic!("copy event len overflow");
         }
     }
-    fn copy_event_total_len(&self) -> usize {
+    pub(crate) fn copy_event_total_len(&self) -> usize {
         self.copy_events
             .iter()
             .map(|c| c.full_length() as usize)
             .sum()
     }
-    /// Push an exponentiation event to the block.
+    /// Push an exponentiation event to the block, unless an event with the
+    /// same base and exponent is already there. The exponentiation table is
+    /// only looked up, so a single event serves all of them.
     pub fn add_exp_event(&mut self, event: ExpEvent) {
-        self.exp_events.push(event);
+        let multiplicity = self
+            .exp_event_multiplicities
+            .entry((event.base, event.exponent))
+            .or_default();
+        if *multiplicity == 0 {
+            self.exp_events.push(event);
+        }
+        *multiplicity += 1;
+    }
+    /// Return the number of exponentiations done with the base and exponent of
+    /// an exponentiation event.
+    pub fn exp_event_multiplicity(&self, event: &ExpEvent) -> usize {
+        self.exp_event_multiplicities
+            .get(&(event.base, event.exponent))
+            .copied()
+            .unwrap_or_default()
     }
     /// Push a precompile event to the block.
     pub fn add_precompile_event(&mut self, event: PrecompileEvent) {
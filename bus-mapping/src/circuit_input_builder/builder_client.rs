@@ -1,8 +1,10 @@
 use eth_types::{
+    evm_types::block_utils::block_blob_base_fee,
     geth_types::{self, Account, BlockConstants},
     state_db::{self, CodeDB, StateDB},
     utils::hash_code_keccak,
-    Address, EthBlock, GethExecTrace, ToWord, Word, H256, KECCAK_CODE_HASH_EMPTY,
+    Address, EthBlock, GethExecTrace, GethPrestateTrace, ToWord, Word, H256,
+    KECCAK_CODE_HASH_EMPTY,
 };
 use ethers_providers::JsonRpcClient;
 use external_tracer::TraceConfig;
@@ -11,7 +13,10 @@ use hex::decode_to_slice;
 use super::{AccessSet, Block, BlockHead, CircuitInputBuilder, CircuitsParams};
 use crate::{error::Error, rpc::GethClient};
 
-use std::{collections::HashMap, iter};
+use std::{
+    collections::{BTreeMap, HashMap},
+    iter,
+};
 
 /// Struct that wraps a GethClient and contains methods to perform all the steps
 /// necessary to generate the circuit inputs for a block by querying geth for
@@ -186,12 +191,27 @@ impl<P: JsonRpcClient> BuilderClient<P> {
             HashMap<Address, Vec<u8>>,
         ),
         Error,
+    > {
+        Self::pre_state_from_prestate_traces(traces.map(|tr| tr.prestate.clone()))
+    }
+
+    /// Build account state and codes from the outputs of the prestate tracer,
+    /// see [`Self::get_pre_state`].
+    #[allow(clippy::type_complexity)]
+    fn pre_state_from_prestate_traces(
+        prestate_traces: impl Iterator<Item = HashMap<Address, GethPrestateTrace>>,
+    ) -> Result<
+        (
+            Vec<eth_types::EIP1186ProofResponse>,
+            HashMap<Address, Vec<u8>>,
+        ),
+        Error,
     > {
         let mut account_set =
             HashMap::<Address, (eth_types::EIP1186ProofResponse, HashMap<Word, Word>)>::new();
         let mut code_set = HashMap::new();
 
-        for trace in traces.map(|tr| tr.prestate.clone()) {
+        for trace in prestate_traces {
             for (addr, prestate) in trace.into_iter() {
                 let (_, storages) = account_set.entry(addr).or_insert_with(|| {
                     let code_size =
@@ -451,6 +471,52 @@ impl<P: JsonRpcClient> BuilderClient<P> {
         Ok(builder)
     }
 
+    /// Generate the circuit inputs of a standalone call, like
+    /// `debug_traceCall`: `tx` is executed as the only transaction of block
+    /// `block_num`, on top of the state of its parent, with the accounts in
+    /// `state_overrides` replacing the ones of that state. Block 0 has no
+    /// parent to run the call on top of.
+    pub async fn gen_inputs_call(
+        &self,
+        block_num: u64,
+        tx: eth_types::Transaction,
+        state_overrides: BTreeMap<Address, Account>,
+    ) -> Result<CircuitInputBuilder, Error> {
+        let (eth_block, trace_config) = self
+            .get_call_trace_config(block_num, tx, &state_overrides)
+            .await?;
+        self.trace_to_builder(&eth_block, &trace_config)
+    }
+
+    /// Block holding `tx` alone and the config to trace it, see
+    /// [`Self::gen_inputs_call`].
+    async fn get_call_trace_config(
+        &self,
+        block_num: u64,
+        mut tx: eth_types::Transaction,
+        state_overrides: &BTreeMap<Address, Account>,
+    ) -> Result<(EthBlock, TraceConfig), Error> {
+        let parent_num = block_num.checked_sub(1).ok_or(Error::InternalError(
+            "a call can not be traced on top of the genesis block parent",
+        ))?;
+        let mut eth_block = self.cli.get_block_by_number(block_num.into()).await?;
+        tx.block_hash = eth_block.hash;
+        tx.block_number = eth_block.number;
+        tx.transaction_index = Some(0.into());
+        eth_block.transactions = vec![tx.clone()];
+
+        // The overrides are applied by the node, so that the prestate holds
+        // the overridden accounts and the ones only reached through them.
+        let prestate = self
+            .cli
+            .trace_call_prestate(&tx, parent_num.into(), state_overrides)
+            .await?;
+        let (proofs, codes) = Self::pre_state_from_prestate_traces(iter::once(prestate))?;
+        let proofs = self.complete_prestate(&eth_block, proofs).await?;
+        let trace_config = self.trace_config_from_pre_state(&eth_block, proofs, codes);
+        Ok((eth_block, trace_config))
+    }
+
     async fn get_trace_config(
         &self,
         eth_block: &EthBlock,
@@ -463,7 +529,16 @@ impl<P: JsonRpcClient> BuilderClient<P> {
         } else {
             proofs
         };
-        Ok(TraceConfig {
+        Ok(self.trace_config_from_pre_state(eth_block, proofs, codes))
+    }
+
+    fn trace_config_from_pre_state(
+        &self,
+        eth_block: &EthBlock,
+        proofs: Vec<eth_types::EIP1186ProofResponse>,
+        codes: HashMap<Address, Vec<u8>>,
+    ) -> TraceConfig {
+        TraceConfig {
             chain_id: self.chain_id,
             history_hashes: vec![eth_block.parent_hash.to_word()],
             block_constants: BlockConstants {
@@ -473,6 +548,7 @@ impl<P: JsonRpcClient> BuilderClient<P> {
                 difficulty: eth_block.difficulty,
                 gas_limit: eth_block.gas_limit,
                 base_fee: eth_block.base_fee_per_gas.unwrap(),
+                blob_base_fee: block_blob_base_fee(eth_block),
             },
             accounts: proofs
                 .into_iter()
@@ -504,7 +580,7 @@ impl<P: JsonRpcClient> BuilderClient<P> {
             chain_config: None,
             #[cfg(feature = "scroll")]
             l1_queue_index: 0,
-        })
+        }
     }
 
     #[cfg(feature = "scroll")]
@@ -547,3 +623,146 @@ impl<P: JsonRpcClient> BuilderClient<P> {
         Ok(builder)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::BuilderClient;
+    use crate::{circuit_input_builder::CircuitsParams, rpc::GethClient};
+    use eth_types::{
+        geth_types::Account, Address, Bytes, EthBlock, GethPrestateTrace, Transaction, Word, H256,
+    };
+    use ethers_providers::MockProvider;
+    use serde_json::json;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn builder_client(provider: &MockProvider) -> BuilderClient<MockProvider> {
+        BuilderClient {
+            cli: GethClient(provider.clone()),
+            chain_id: 1,
+            circuits_params: CircuitsParams::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn call_trace_config_from_overridden_prestate() {
+        let from = Address::repeat_byte(0x11);
+        let to = Address::repeat_byte(0x22);
+        let coinbase = Address::repeat_byte(0x33);
+        let code = Bytes::from(vec![0x60, 0x01, 0x54, 0x00]);
+        let tx = Transaction {
+            from,
+            to: Some(to),
+            gas: 100_000.into(),
+            gas_price: Some(1.into()),
+            ..Default::default()
+        };
+        let state_overrides = BTreeMap::from([(
+            to,
+            Account {
+                address: to,
+                nonce: 1.into(),
+                code: code.clone(),
+                storage: HashMap::from([(Word::one(), Word::from(2))]),
+                ..Default::default()
+            },
+        )]);
+        // The node reports the overridden accounts in the prestate.
+        let prestate = HashMap::from([
+            (
+                from,
+                GethPrestateTrace {
+                    balance: Some(Word::from(10).pow(18.into())),
+                    nonce: Some(0),
+                    code: None,
+                    storage: None,
+                },
+            ),
+            (
+                to,
+                GethPrestateTrace {
+                    balance: Some(Word::zero()),
+                    nonce: Some(1),
+                    code: Some(code.clone()),
+                    storage: Some(HashMap::from([(Word::one(), Word::from(2))])),
+                },
+            ),
+            (
+                coinbase,
+                GethPrestateTrace {
+                    balance: Some(Word::zero()),
+                    nonce: Some(0),
+                    code: None,
+                    storage: None,
+                },
+            ),
+        ]);
+        let eth_block = EthBlock {
+            hash: Some(H256::repeat_byte(0xbb)),
+            number: Some(2.into()),
+            author: Some(coinbase),
+            base_fee_per_gas: Some(1.into()),
+            ..Default::default()
+        };
+
+        let provider = MockProvider::new();
+        // The responses are popped in the reverse order.
+        provider.push(prestate).unwrap();
+        provider.push(eth_block).unwrap();
+        let (eth_block, trace_config) = builder_client(&provider)
+            .get_call_trace_config(2, tx, &state_overrides)
+            .await
+            .unwrap();
+
+        provider
+            .assert_request("eth_getBlockByNumber", json!(["0x2", true]))
+            .unwrap();
+        let call = json!({
+            "from": from,
+            "to": to,
+            "gas": "0x186a0",
+            "gasPrice": "0x1",
+            "maxFeePerGas": null,
+            "maxPriorityFeePerGas": null,
+            "value": "0x0",
+            "data": "0x",
+        });
+        let cfg = json!({
+            "tracer": "prestateTracer",
+            "stateOverrides": {
+                format!("{to:?}"): {
+                    "nonce": "0x1",
+                    "balance": "0x0",
+                    "code": "0x60015400",
+                    "state": {
+                        format!("{:?}", H256::from_low_u64_be(1)): format!("{:?}", H256::from_low_u64_be(2)),
+                    },
+                },
+            },
+        });
+        provider
+            .assert_request("debug_traceCall", json!([call, "0x1", cfg]))
+            .unwrap();
+
+        assert_eq!(eth_block.transactions.len(), 1);
+        assert_eq!(eth_block.transactions[0].block_number, Some(2.into()));
+        let account = &trace_config.accounts[&to];
+        assert_eq!(
+            (account.nonce, &account.code, &account.storage),
+            (
+                Word::one(),
+                &code,
+                &HashMap::from([(Word::one(), Word::from(2))])
+            )
+        );
+        assert!(trace_config.accounts.contains_key(&coinbase));
+    }
+
+    #[tokio::test]
+    async fn call_trace_config_rejects_genesis_block() {
+        let provider = MockProvider::new();
+        assert!(builder_client(&provider)
+            .get_call_trace_config(0, Transaction::default(), &BTreeMap::new())
+            .await
+            .is_err());
+    }
+}
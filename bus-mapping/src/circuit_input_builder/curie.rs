@@ -13,15 +13,6 @@ use crate::{
 
 use super::{CircuitInputStateRef, ExecStep};
 
-/// Whether this blk is the hardfork height of curie
-pub fn is_curie_fork(chain_id: u64, blk: u64) -> bool {
-    if chain_id == 222222 && blk == 5 {
-        log::info!("enable curie fork: chain id {chain_id} block {blk}");
-        return true;
-    }
-    false
-}
-
 /// Insert needed rws for the contract upgrade
 /// Num of rws: 7
 pub fn apply_curie(state: &mut CircuitInputStateRef, step: &mut ExecStep) -> Result<(), Error> {
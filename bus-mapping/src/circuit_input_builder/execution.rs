@@ -2,7 +2,7 @@
 
 use std::{
     marker::PhantomData,
-    ops::{Add, Mul, Neg},
+    ops::{Add, Mul, Neg, Range},
 };
 
 use crate::{
@@ -529,6 +529,69 @@ impl CopyEvent {
     }
 }
 
+/// A copy event whose RW counter range is inconsistent with the RW table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CopyRwOverlap {
+    /// Index of the copy event in [`Block::copy_events`](super::Block)
+    pub event: usize,
+    /// RW counters consumed by the copy event
+    pub rw_counters: Range<u64>,
+    /// Index of an earlier copy event whose RW counters intersect this one
+    pub overlaps_with: Option<usize>,
+    /// RW counters in the range without an operation in the RW table
+    pub missing: Vec<u64>,
+}
+
+/// An invariant of a [`GethExecTrace`](eth_types::GethExecTrace) which does
+/// not hold at one of its steps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceViolation {
+    /// Index of the step in the struct logs of the trace
+    pub step: usize,
+    /// Invariant which does not hold
+    pub kind: TraceViolationKind,
+}
+
+/// Kind of a [`TraceViolation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceViolationKind {
+    /// The first step is not executed at depth 1.
+    InitialDepth(u16),
+    /// The depth of the next step changes by more than one, or increases
+    /// after an opcode which is neither a call nor a create.
+    DepthTransition {
+        /// Depth of the step
+        depth: u16,
+        /// Depth of the next step
+        next_depth: u16,
+    },
+    /// The gas left increases between two steps of the same call.
+    GasIncrease {
+        /// Gas left at the step
+        gas: u64,
+        /// Gas left at the next step
+        next_gas: u64,
+    },
+    /// The stack size is out of the valid range of an opcode executed without
+    /// error.
+    StackSize {
+        /// Opcode of the step
+        op: OpcodeId,
+        /// Size of the stack before the step
+        size: usize,
+    },
+    /// RETURNDATACOPY copies past the end of the return data of the last
+    /// callee, but the step has no error.
+    ReturnDataOutOfBounds {
+        /// Offset in the return data
+        offset: Word,
+        /// Length of the copy
+        length: Word,
+        /// Length of the return data of the last callee
+        return_data_len: u64,
+    },
+}
+
 /// Defines a builder to construct a copy event.
 ///
 /// ```markdown
@@ -988,6 +1051,20 @@ impl PrecompileEvents {
             .cloned()
             .collect()
     }
+    /// Get all Blake2F events.
+    pub fn get_blake2f_events(&self) -> Vec<Blake2fOp> {
+        self.events
+            .iter()
+            .filter_map(|e| {
+                if let PrecompileEvent::Blake2F(op) = e {
+                    Some(op.as_ref())
+                } else {
+                    None
+                }
+            })
+            .cloned()
+            .collect()
+    }
 }
 
 /// I/O from a precompiled contract call.
@@ -1005,6 +1082,8 @@ pub enum PrecompileEvent {
     ModExp(BigModExp),
     /// Represents the I/O from SHA256 call.
     SHA256(SHA256),
+    /// Represents the I/O from Blake2F call.
+    Blake2F(Box<Blake2fOp>),
 }
 
 impl Default for PrecompileEvent {
@@ -1452,6 +1531,53 @@ impl Default for BigModExp {
     }
 }
 
+/// Blake2F compression `F(h, m, t, f)` with `rounds` rounds, i.e. the I/O of
+/// a successful call to the blake2f precompile (EIP-152).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Blake2fOp {
+    /// Number of rounds.
+    pub rounds: u32,
+    /// State vector.
+    pub h: [u64; 8],
+    /// Message block vector.
+    pub m: [u64; 16],
+    /// Offset counters.
+    pub t: [u64; 2],
+    /// Final block indicator flag.
+    pub f: bool,
+    /// Compressed state vector.
+    pub output: [u64; 8],
+}
+
+impl Blake2fOp {
+    /// Length of a valid blake2f input.
+    pub const INPUT_LEN: usize = 213;
+    /// Length of the blake2f output.
+    pub const OUTPUT_LEN: usize = 64;
+
+    /// Parse the blake2f input and output bytes. Returns `None` if the input
+    /// is not a valid blake2f input, i.e. the precompile call fails.
+    pub fn new_from_bytes(input: &[u8], output: &[u8]) -> Option<Self> {
+        if input.len() != Self::INPUT_LEN || input[212] > 1 || output.len() != Self::OUTPUT_LEN {
+            return None;
+        }
+        fn read_u64s<const N: usize>(bytes: &[u8]) -> [u64; N] {
+            std::array::from_fn(|i| {
+                u64::from_le_bytes(bytes[8 * i..8 * (i + 1)].try_into().unwrap())
+            })
+        }
+
+        Some(Self {
+            rounds: u32::from_be_bytes(input[0..4].try_into().unwrap()),
+            h: read_u64s(&input[4..68]),
+            m: read_u64s(&input[68..196]),
+            t: read_u64s(&input[196..212]),
+            f: input[212] == 1,
+            output: read_u64s(output),
+        })
+    }
+}
+
 /// Event representating an SHA256 hash in precompile sha256.
 #[derive(Clone, Debug, Default)]
 pub struct SHA256 {
@@ -16,8 +16,8 @@ use crate::{
     exec_trace::OperationRef,
     operation::{
         AccountField, AccountOp, CallContextField, CallContextOp, MemoryOp, Op, OpEnum, Operation,
-        StackOp, Target, TxAccessListAccountOp, TxAccessListAccountStorageOp, TxLogField, TxLogOp,
-        TxReceiptField, TxReceiptOp, RW,
+        StackOp, Target, TxAccessListAccountOp, TxAccessListAccountStorageOp, TxCreatedAccountOp,
+        TxLogField, TxLogOp, TxReceiptField, TxReceiptOp, RW,
     },
     precompile::PrecompileCalls,
     Error,
@@ -351,18 +351,22 @@ impl<'a> CircuitInputStateRef<'a> {
     /// adds a reference to the stored operation ([`OperationRef`]) inside
     /// the bus-mapping instance of the current [`ExecStep`].  Then increase
     /// the `block_ctx` [`RWCounter`](crate::operation::RWCounter)  by one.
+    /// As for the other copy steps, the caller memory is left as is when it
+    /// is taken from the trace.
     pub fn memory_write_caller(
         &mut self,
         step: &mut ExecStep,
         address: MemoryAddress, //Caution: make sure this address = slot passing
         value: Word,
     ) -> Result<Vec<u8>, Error> {
+        let skip_write = self.skip_copy_memory_writes()?;
         let mem = &mut self.caller_ctx_mut()?.memory;
         let value_prev = mem.read_word(address);
         let value_prev_bytes = value_prev.to_be_bytes();
 
-        let value_bytes = value.to_be_bytes();
-        mem.write_chunk(address, &value_bytes);
+        if !skip_write {
+            mem.write_chunk(address, &value.to_be_bytes());
+        }
 
         let call_id = self.call()?.caller_id;
         self.push_op(
@@ -678,6 +682,49 @@ impl<'a> CircuitInputStateRef<'a> {
         )
     }
 
+    /// Mark the account at `address` as created in the current transaction.
+    pub fn tx_created_account_write(
+        &mut self,
+        step: &mut ExecStep,
+        address: Address,
+    ) -> Result<(), Error> {
+        let is_created = self.sdb.check_account_created(&address);
+        self.push_op_reversible(
+            step,
+            TxCreatedAccountOp {
+                tx_id: self.tx_ctx.id(),
+                address,
+                is_created: true,
+                is_created_prev: is_created,
+            },
+        )
+    }
+
+    /// Push a read type [`TxCreatedAccountOp`] into the
+    /// [`OperationContainer`](crate::operation::OperationContainer) with the
+    /// next [`RWCounter`](crate::operation::RWCounter), and then
+    /// adds a reference to the stored operation ([`OperationRef`]) inside
+    /// the bus-mapping instance of the current [`ExecStep`].  Then increase
+    /// the `block_ctx` [`RWCounter`](crate::operation::RWCounter)  by one.
+    pub fn tx_created_account_read(
+        &mut self,
+        step: &mut ExecStep,
+        address: Address,
+    ) -> Result<bool, Error> {
+        let is_created = self.sdb.check_account_created(&address);
+        self.push_op(
+            step,
+            RW::READ,
+            TxCreatedAccountOp {
+                tx_id: self.tx_ctx.id(),
+                address,
+                is_created,
+                is_created_prev: is_created,
+            },
+        )?;
+        Ok(is_created)
+    }
+
     /// Add address storage key to access list for the current transaction.
     pub fn tx_access_list_storage_key_write(
         &mut self,
@@ -1049,6 +1096,50 @@ impl<'a> CircuitInputStateRef<'a> {
         Ok(())
     }
 
+    /// Whether the copy steps leave the copied bytes out of the destination
+    /// memory, which is taken from the trace afterwards by
+    /// [`Self::take_trace_memory`]. They are still written when the trace is
+    /// checked, and by the calls into precompiles, which have no steps in the
+    /// trace.
+    fn skip_copy_memory_writes(&self) -> Result<bool, Error> {
+        #[cfg(feature = "enable-memory")]
+        if self.block_ctx.trace_memory && !self.block_ctx.check_trace_memory {
+            let code_address = self.call()?.code_address();
+            return Ok(!code_address.map_or(false, |address| is_precompiled(&address)));
+        }
+        Ok(false)
+    }
+
+    /// Take the memory of the current call from `next_step`, the step of the
+    /// trace following a copy into it, when the copy skipped the writes. When
+    /// the trace is checked, the reconstructed memory must match it instead.
+    /// Nothing is done when the call ended without a further step.
+    #[cfg(feature = "enable-memory")]
+    pub(crate) fn take_trace_memory(
+        &mut self,
+        next_step: Option<&GethExecStep>,
+    ) -> Result<(), Error> {
+        if !self.block_ctx.trace_memory {
+            return Ok(());
+        }
+        let depth = self.call()?.depth;
+        let Some(next_step) = next_step.filter(|step| step.depth as usize == depth) else {
+            return Ok(());
+        };
+        let check = self.block_ctx.check_trace_memory;
+        let memory = &mut self.call_ctx_mut()?.memory;
+        if check {
+            assert_eq!(
+                *memory, next_step.memory,
+                "reconstructed memory differs from the trace before {:?} at pc {}",
+                next_step.op, next_step.pc.0
+            );
+        } else {
+            *memory = next_step.memory.clone();
+        }
+        Ok(())
+    }
+
     /// Check if address is a precompiled or not.
     pub fn is_precompiled(&self, address: &Address) -> bool {
         address.0[0..19] == [0u8; 19] && (1..=9).contains(&address.0[19])
@@ -1204,6 +1295,14 @@ impl<'a> CircuitInputStateRef<'a> {
                     None
                 }
             }
+            OperationRef(Target::TxCreatedAccount, idx) => {
+                let operation = &self.block.container.tx_created_account[*idx];
+                if operation.rw().is_write() && operation.reversible() {
+                    Some(OpEnum::TxCreatedAccount(operation.op().reverse()))
+                } else {
+                    None
+                }
+            }
             OperationRef(Target::TxRefund, idx) => {
                 let operation = &self.block.container.tx_refund[*idx];
                 if operation.rw().is_write() && operation.reversible() {
@@ -1252,6 +1351,14 @@ impl<'a> CircuitInputStateRef<'a> {
                         .remove_account_storage_from_access_list(&(op.address, op.key));
                 }
             }
+            OpEnum::TxCreatedAccount(op) => {
+                if !op.is_created_prev && op.is_created {
+                    self.sdb.add_created_account(op.address);
+                }
+                if op.is_created_prev && !op.is_created {
+                    self.sdb.remove_created_account(&op.address);
+                }
+            }
             OpEnum::Account(op) => self.check_update_sdb_account(RW::WRITE, op),
             OpEnum::TxRefund(op) => {
                 self.sdb.set_refund(op.value);
@@ -2131,16 +2238,27 @@ impl<'a> CircuitInputStateRef<'a> {
             return Ok((vec![], vec![], vec![]));
         }
 
-        let caller_memory = self.caller_ctx()?.memory.clone();
         let call_data_length = self.call()?.call_data_length;
         let call_data_offset = self.call()?.call_data_offset;
+        // Borrow the caller memory alongside the current call context instead
+        // of cloning it.
+        let (call_ctx, caller_ctxs) =
+            self.tx_ctx
+                .calls
+                .split_last_mut()
+                .ok_or(Error::InvalidGethExecTrace(
+                    "Call stack is empty but call is used",
+                ))?;
+        let caller_memory = &caller_ctxs
+            .last()
+            .ok_or(Error::InternalError("caller id not found in call map"))?
+            .memory;
         let call_data = if call_data_length != 0 {
             let ends = call_data_offset + call_data_length;
             &caller_memory.0[..ends as usize]
         } else {
             &caller_memory.0[..call_data_offset as usize]
         };
-        let call_ctx = self.call_ctx_mut()?;
         let (src_range, dst_range, write_slot_bytes) = combine_copy_slot_bytes(
             src_addr.into().0,
             dst_addr.into().0,
@@ -2193,16 +2311,19 @@ impl<'a> CircuitInputStateRef<'a> {
             return Ok((vec![], vec![], vec![]));
         }
 
-        let last_callee_memory = self.call()?.last_callee_memory.clone();
-        let return_data_length = self.call()?.last_callee_return_data_length;
-        let return_data_offset = self.call()?.last_callee_return_data_offset;
+        // The current call lives in `tx` while its context lives in `tx_ctx`,
+        // so the callee memory can be borrowed instead of cloned.
+        let call = &self.tx.calls()[self.tx_ctx.call_index()?];
+        let last_callee_memory = &call.last_callee_memory;
+        let return_data_length = call.last_callee_return_data_length;
+        let return_data_offset = call.last_callee_return_data_offset;
         let return_data: &[u8] = if return_data_length != 0 {
             let ends = return_data_offset + return_data_length;
             &last_callee_memory.0[..ends as usize]
         } else {
             &last_callee_memory.0[..return_data_offset as usize]
         };
-        let call_ctx = self.call_ctx_mut()?;
+        let call_ctx = self.tx_ctx.call_ctx_mut()?;
         let (src_range, dst_range, write_slot_bytes) = combine_copy_slot_bytes(
             src_addr.into().0,
             dst_addr.into().0,
@@ -2387,9 +2508,21 @@ impl<'a> CircuitInputStateRef<'a> {
     ) -> Result<(), Error> {
         assert_eq!(write_chunk.len(), 32);
         let write_word = Word::from_big_endian(write_chunk);
-        let mut prev_bytes_write =
-            self.memory_write_word(exec_step, dst_chunk_index.into(), write_word)?;
-        prev_bytes.append(&mut prev_bytes_write);
+        if self.skip_copy_memory_writes()? {
+            let address = dst_chunk_index.into();
+            let value_prev = self.call_ctx()?.memory.read_word(address);
+            let call_id = self.call()?.call_id;
+            self.push_op(
+                exec_step,
+                RW::WRITE,
+                MemoryOp::new_write(call_id, address, write_word, value_prev),
+            )?;
+            prev_bytes.extend_from_slice(&value_prev.to_be_bytes());
+        } else {
+            let mut prev_bytes_write =
+                self.memory_write_word(exec_step, dst_chunk_index.into(), write_word)?;
+            prev_bytes.append(&mut prev_bytes_write);
+        }
         //trace!("write chunk: {} {dst_chunk_index} {write_chunk:?}", self.call()?.call_id);
 
         Ok(())
@@ -107,6 +107,7 @@ impl CircuitInputBuilder {
             block: block.clone(),
             block_ctx: BlockContext::new(),
             mpt_init_state: Some(mpt_init_state),
+            opcode_timings: None,
         }
     }
 
@@ -185,6 +186,7 @@ impl CircuitInputBuilder {
             block: builder_block,
             block_ctx: BlockContext::new(),
             mpt_init_state,
+            opcode_timings: None,
         };
 
         builder.apply_l2_trace(l2_trace, !more)?;
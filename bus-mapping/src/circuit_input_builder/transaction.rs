@@ -210,6 +210,8 @@ pub struct Transaction {
     pub l1_fee_committed: TxL1Fee,
     /// EIP2930
     pub access_list: Option<AccessList>,
+    /// Blob versioned hashes (EIP-4844), read by BLOBHASH
+    pub blob_versioned_hashes: Vec<H256>,
     /// Calls made in the transaction
     pub(crate) calls: Vec<Call>,
     /// Execution steps
@@ -269,6 +271,7 @@ impl Transaction {
             l1_fee: Default::default(),
             l1_fee_committed: Default::default(),
             access_list: None,
+            blob_versioned_hashes: vec![],
         }
     }
 
@@ -382,6 +385,13 @@ impl Transaction {
             l1_fee,
             l1_fee_committed,
             access_list: eth_tx.access_list.clone(),
+            // only set for blob txs
+            blob_versioned_hashes: eth_tx
+                .other
+                .get_deserialized("blobVersionedHashes")
+                .transpose()
+                .map_err(Error::SerdeError)?
+                .unwrap_or_default(),
         })
     }
 
@@ -390,6 +400,18 @@ impl Transaction {
         self.calls[0].is_create()
     }
 
+    /// Price per unit of gas paid by the sender under the given block base
+    /// fee. For EIP-1559 transactions this is `min(gas_fee_cap, base_fee +
+    /// gas_tip_cap)`, for the other types it is the plain `gas_price`.
+    pub fn effective_gas_price(&self, base_fee: Word) -> Word {
+        if self.tx_type.is_eip1559() {
+            self.gas_fee_cap
+                .min(base_fee.saturating_add(self.gas_tip_cap))
+        } else {
+            self.gas_price
+        }
+    }
+
     /// Return the list of execution steps of this transaction.
     pub fn steps(&self) -> &[ExecStep] {
         &self.steps
@@ -215,6 +215,13 @@ pub(crate) fn get_step_reported_error(op: &OpcodeId, error: GethExecError) -> Ex
             };
             ExecError::OutOfGas(oog_err)
         }
+        // EIP-3860: the oversized init code is handled by the same error step
+        // as an out of gas CREATE/CREATE2.
+        GethExecError::MaxInitCodeSizeExceeded
+            if matches!(op, OpcodeId::CREATE | OpcodeId::CREATE2) =>
+        {
+            ExecError::OutOfGas(OogError::Create)
+        }
         GethExecError::StackOverflow { .. } => ExecError::StackOverflow,
         GethExecError::StackUnderflow { .. } => ExecError::StackUnderflow,
         GethExecError::WriteProtection => ExecError::WriteProtection,
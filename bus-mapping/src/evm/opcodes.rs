@@ -6,11 +6,10 @@ use crate::{
         NonceUintOverflowError, OogError,
     },
     evm::OpcodeId,
-    operation::{AccountField, AccountOp, TxAccessListAccountOp},
     Error,
 };
 use core::fmt::Debug;
-use eth_types::{evm_unimplemented, GethExecStep, ToAddress, ToWord, Word};
+use eth_types::{evm_unimplemented, GethExecStep};
 
 #[cfg(any(feature = "enable-memory", feature = "enable-stack"))]
 use crate::util::GETH_TRACE_CHECK_LEVEL;
@@ -22,6 +21,7 @@ mod address;
 mod arithmetic;
 mod balance;
 mod begin_end_tx;
+mod blobhash;
 mod blockhash;
 mod calldatacopy;
 mod calldataload;
@@ -32,6 +32,7 @@ mod callvalue;
 mod chainid;
 mod codecopy;
 mod codesize;
+mod copyop;
 mod create;
 mod dup;
 mod environment;
@@ -54,6 +55,7 @@ mod return_revert;
 mod returndatacopy;
 mod returndatasize;
 mod selfbalance;
+mod selfdestruct;
 mod sha3;
 mod sload;
 mod sstore;
@@ -64,7 +66,6 @@ mod tload;
 mod tstore;
 
 mod error_codestore;
-mod error_contract_address_collision;
 mod error_invalid_creation_code;
 mod error_invalid_jump;
 mod error_oog_account_access;
@@ -88,6 +89,7 @@ use address::Address;
 use arithmetic::ArithmeticOpcode;
 use balance::Balance;
 use begin_end_tx::{gen_begin_tx_steps, gen_end_tx_steps};
+use blobhash::Blobhash;
 use blockhash::Blockhash;
 use calldatacopy::Calldatacopy;
 use calldataload::Calldataload;
@@ -126,6 +128,7 @@ use return_revert::ReturnRevert;
 use returndatacopy::Returndatacopy;
 use returndatasize::Returndatasize;
 use selfbalance::Selfbalance;
+use selfdestruct::Selfdestruct;
 use sload::Sload;
 use sstore::Sstore;
 use stackonlyop::StackPopOnlyOpcode;
@@ -236,6 +239,10 @@ fn fn_gen_associated_ops(opcode_id: &OpcodeId) -> FnGenAssociatedOps {
         OpcodeId::CHAINID => GetBlockHeaderField::<{ OpcodeId::CHAINID }>::gen_associated_ops,
         OpcodeId::SELFBALANCE => Selfbalance::gen_associated_ops,
         OpcodeId::BASEFEE => GetBlockHeaderField::<{ OpcodeId::BASEFEE }>::gen_associated_ops,
+        OpcodeId::BLOBHASH => Blobhash::gen_associated_ops,
+        OpcodeId::BLOBBASEFEE => {
+            GetBlockHeaderField::<{ OpcodeId::BLOBBASEFEE }>::gen_associated_ops
+        }
         OpcodeId::POP => StackPopOnlyOpcode::<1>::gen_associated_ops,
         OpcodeId::MCOPY => MCopy::gen_associated_ops,
         OpcodeId::MLOAD => Mload::gen_associated_ops,
@@ -294,10 +301,7 @@ fn fn_gen_associated_ops(opcode_id: &OpcodeId) -> FnGenAssociatedOps {
         OpcodeId::CREATE2 => Create::<true>::gen_associated_ops,
         OpcodeId::RETURN | OpcodeId::REVERT => ReturnRevert::gen_associated_ops,
         OpcodeId::INVALID(_) => Stop::gen_associated_ops,
-        OpcodeId::SELFDESTRUCT => {
-            log::debug!("Using dummy gen_selfdestruct_ops for opcode SELFDESTRUCT");
-            DummySelfDestruct::gen_associated_ops
-        }
+        OpcodeId::SELFDESTRUCT => Selfdestruct::gen_associated_ops,
         _ => {
             log::debug!("Using dummy gen_associated_ops for opcode {:?}", opcode_id);
             Dummy::gen_associated_ops
@@ -350,6 +354,9 @@ fn fn_gen_error_state_associated_ops(
         ExecError::OutOfGas(OogError::AccountAccess) => {
             Some(ErrorOOGAccountAccess::gen_associated_ops)
         }
+        ExecError::OutOfGas(OogError::SelfDestruct) => {
+            Some(StackPopOnlyOpcode::<1, true>::gen_associated_ops)
+        }
         // ExecError::
         ExecError::StackOverflow => Some(StackPopOnlyOpcode::<0, true>::gen_associated_ops),
         ExecError::StackUnderflow => Some(StackPopOnlyOpcode::<0, true>::gen_associated_ops),
@@ -548,110 +555,3 @@ pub fn gen_associated_steps(
 
     fn_gen_associated_steps(state)
 }
-
-#[derive(Debug, Copy, Clone)]
-struct DummySelfDestruct;
-
-impl Opcode for DummySelfDestruct {
-    fn gen_associated_ops(
-        state: &mut CircuitInputStateRef,
-        geth_steps: &[GethExecStep],
-    ) -> Result<Vec<ExecStep>, Error> {
-        dummy_gen_selfdestruct_ops(state, geth_steps)
-    }
-}
-fn dummy_gen_selfdestruct_ops(
-    state: &mut CircuitInputStateRef,
-    geth_steps: &[GethExecStep],
-) -> Result<Vec<ExecStep>, Error> {
-    let geth_step = &geth_steps[0];
-    let mut exec_step = state.new_step(geth_step)?;
-    let sender = state.call()?.address;
-    let receiver = state.call_ctx_mut()?.stack.pop()?.to_address();
-    #[cfg(feature = "enable-stack")]
-    assert_eq!(receiver, geth_step.stack.last()?.to_address());
-
-    let is_warm = state.sdb.check_account_in_access_list(&receiver);
-    state.push_op_reversible(
-        &mut exec_step,
-        TxAccessListAccountOp {
-            tx_id: state.tx_ctx.id(),
-            address: receiver,
-            is_warm: true,
-            is_warm_prev: is_warm,
-        },
-    )?;
-
-    let (found, receiver_account) = state.sdb.get_account(&receiver);
-    if !found {
-        return Err(Error::AccountNotFound(receiver));
-    }
-    let receiver_account = &receiver_account.clone();
-    let (found, sender_account) = state.sdb.get_account(&sender);
-    if !found {
-        return Err(Error::AccountNotFound(sender));
-    }
-    let sender_account = &sender_account.clone();
-    let value = sender_account.balance;
-    log::trace!(
-        "self destruct, sender {:?} receiver {:?} value {:?}",
-        sender,
-        receiver,
-        value
-    );
-    // NOTE: In this dummy implementation we assume that the receiver already
-    // exists.
-
-    state.push_op_reversible(
-        &mut exec_step,
-        AccountOp {
-            address: sender,
-            field: AccountField::Balance,
-            value: Word::zero(),
-            value_prev: value,
-        },
-    )?;
-    state.push_op_reversible(
-        &mut exec_step,
-        AccountOp {
-            address: sender,
-            field: AccountField::Nonce,
-            value: Word::zero(),
-            value_prev: sender_account.nonce,
-        },
-    )?;
-    state.push_op_reversible(
-        &mut exec_step,
-        AccountOp {
-            address: sender,
-            field: AccountField::CodeHash,
-            value: Word::zero(),
-            value_prev: sender_account.code_hash.to_word(),
-        },
-    )?;
-    if receiver != sender {
-        state.transfer_to(
-            &mut exec_step,
-            receiver,
-            !receiver_account.is_empty(),
-            false,
-            value,
-            true,
-        )?;
-    }
-
-    if state.call()?.is_persistent {
-        state.sdb.destruct_account(sender);
-    }
-
-    if let Ok(caller) = state.caller_ctx_mut() {
-        caller.return_data.clear();
-    }
-    state.handle_return(
-        (None, None),
-        &mut [&mut exec_step],
-        geth_steps,
-        !state.call()?.is_root,
-    )?;
-    Ok(vec![exec_step])
-}
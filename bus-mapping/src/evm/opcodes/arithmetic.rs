@@ -546,4 +546,24 @@ mod tests {
             word!("0xfffefdfcfbfaf9f8f7f6f5f4f3f2f1f0efeeedecebeae9e8e7e6e5e4e3e2e1e0"),
         );
     }
+
+    #[test]
+    fn test_shl_overflow() {
+        test_both::<2, { OpcodeId::SHL }>([0x100u64.into(), Word::MAX], 0x00u64.into());
+        test_both::<2, { OpcodeId::SHL }>([0xffu64.into(), 0x01u64.into()], Word::one() << 255);
+    }
+
+    #[test]
+    fn test_shr_overflow() {
+        test_both::<2, { OpcodeId::SHR }>([0x12cu64.into(), Word::MAX], 0x00u64.into());
+        test_both::<2, { OpcodeId::SHR }>([Word::MAX, Word::MAX], 0x00u64.into());
+    }
+
+    #[test]
+    fn test_sar_overflow() {
+        let neg = word!("0x8000000000000000000000000000000000000000000000000000000000000001");
+        test_both::<2, { OpcodeId::SAR }>([0x100u64.into(), neg], Word::MAX);
+        test_both::<2, { OpcodeId::SAR }>([Word::MAX, neg], Word::MAX);
+        test_both::<2, { OpcodeId::SAR }>([0x100u64.into(), SignedWord::MAX.0], 0x00u64.into());
+    }
 }
@@ -21,7 +21,7 @@ use eth_types::{
     },
     state_db::CodeDB,
     utils::is_precompiled,
-    Bytecode, ToWord, Word,
+    Address, Bytecode, ToWord, Word,
 };
 use ethers_core::utils::get_contract_address;
 
@@ -257,11 +257,32 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
         }
     }
 
-    // Transfer with fee
+    // Transfer with fee. An EIP-1559 sender must be able to cover the fee cap,
+    // but is only charged the effective gas price (geth `buyGas`).
+    let base_fee = state
+        .block
+        .headers
+        .get(&state.tx.block_num)
+        .unwrap()
+        .base_fee;
+    let gas_price = state.tx.effective_gas_price(base_fee);
+    if gas_price != state.tx.gas_price {
+        return Err(Error::InvalidGethExecTrace(
+            "tx gas price differs from its effective gas price",
+        ));
+    }
     let fee = if state.tx.tx_type.is_l1_msg() {
         0.into()
     } else {
-        state.tx.gas_price * state.tx.gas + state.tx_ctx.l1_fee
+        if state.tx.tx_type.is_eip1559() {
+            let max_fee = state.tx.gas_fee_cap * state.tx.gas + call.value + state.tx_ctx.l1_fee;
+            if state.sdb.get_balance(&call.caller_address) < max_fee {
+                return Err(Error::InvalidGethExecTrace(
+                    "sender balance below gas fee cap, value and l1 fee",
+                ));
+            }
+        }
+        gas_price * state.tx.gas + state.tx_ctx.l1_fee
     };
     state.transfer_with_fee(
         &mut exec_step,
@@ -340,6 +361,7 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
                     value_prev: 0.into(),
                 },
             )?;
+            state.tx_created_account_write(&mut exec_step, call.address)?;
             for (field, value) in [
                 (CallContextField::Depth, call.depth.into()),
                 (
@@ -581,7 +603,14 @@ pub fn gen_end_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Er
         return Err(Error::AccountNotFound(call.caller_address));
     }
     let caller_balance_prev = caller_account.balance;
-    let effective_refund_balance = state.tx.gas_price * (exec_step.gas_left.0 + effective_refund);
+    let block_info = state
+        .block
+        .headers
+        .get(&state.tx.block_num)
+        .unwrap()
+        .clone();
+    let gas_price = state.tx.effective_gas_price(block_info.base_fee);
+    let effective_refund_balance = gas_price * (exec_step.gas_left.0 + effective_refund);
     let caller_balance = caller_balance_prev + effective_refund_balance;
 
     if !state.tx.tx_type.is_l1_msg() {
@@ -601,16 +630,10 @@ pub fn gen_end_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Er
         log::trace!("l1 tx, no refund");
     }
 
-    let block_info = state
-        .block
-        .headers
-        .get(&state.tx.block_num)
-        .unwrap()
-        .clone();
     let effective_tip = if cfg!(feature = "scroll") {
-        state.tx.gas_price
+        gas_price
     } else {
-        state.tx.gas_price - block_info.base_fee
+        gas_price - block_info.base_fee
     };
     let gas_cost = state.tx.gas - exec_step.gas_left.0 - effective_refund;
     let coinbase_reward = if state.tx.tx_type.is_l1_msg() {
@@ -620,7 +643,7 @@ pub fn gen_end_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Er
     };
     log::trace!(
         "coinbase reward = ({} - {}) * ({} - {} - {}) = {} or 0 for l1 msg",
-        state.tx.gas_price,
+        gas_price,
         block_info.base_fee,
         state.tx.gas,
         exec_step.gas_left.0,
@@ -658,9 +681,60 @@ pub fn gen_end_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Er
 
     end_tx(state, &mut exec_step, &call)?;
 
+    // EIP-6780: a contract destroyed in this tx keeps its code, nonce and
+    // storage until the end of the tx, so it is only cleared here.
+    for address in state.sdb.destructed_accounts() {
+        clear_destructed_account(state, &mut exec_step, address)?;
+    }
+
     Ok(exec_step)
 }
 
+fn clear_destructed_account(
+    state: &mut CircuitInputStateRef,
+    exec_step: &mut ExecStep,
+    address: Address,
+) -> Result<(), Error> {
+    for (key, value) in state.sdb.account_storage_dump(&address) {
+        let committed_value = *state.sdb.get_committed_storage(&address, &key).1;
+        state.push_op(
+            exec_step,
+            RW::WRITE,
+            StorageOp::new(
+                address,
+                key,
+                Word::zero(),
+                value,
+                state.tx_ctx.id(),
+                committed_value,
+            ),
+        )?;
+    }
+
+    // Any balance received after the SELFDESTRUCT is burned as well. The code
+    // hash goes last, as it is the only field that can be written once the
+    // account is empty.
+    let account = state.sdb.get_account(&address).1.clone();
+    for (field, value_prev) in [
+        (AccountField::Balance, account.balance),
+        (AccountField::Nonce, account.nonce),
+    ] {
+        state.account_write(exec_step, address, field, Word::zero(), value_prev)?;
+    }
+    let code_hash_prev = if state.sdb.get_account(&address).1.is_empty() {
+        Word::zero()
+    } else {
+        account.code_hash.to_word()
+    };
+    state.account_write(
+        exec_step,
+        address,
+        AccountField::CodeHash,
+        Word::zero(),
+        code_hash_prev,
+    )
+}
+
 pub(crate) fn begin_tx(
     state: &mut CircuitInputStateRef,
     exec_step: &mut ExecStep,
@@ -949,3 +1023,219 @@ fn add_access_list_storage_key_copy_event(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod begin_tx_tests {
+    use crate::{
+        circuit_input_builder::{ExecState, ExecStep},
+        l2_predeployed::l1_gas_price_oracle,
+        mock::BlockData,
+        operation::{AccountField, CallContextField, Target, RW},
+    };
+    use eth_types::{
+        address, evm_types::GasCost, geth_types::GethData, AccessList, AccessListItem, ToWord,
+        Word, H256,
+    };
+    use ethers_signers::Signer;
+    use mock::{eth, gwei, TestContext, MOCK_ACCOUNTS, MOCK_WALLETS};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn begin_tx_warms_access_list() {
+        let listed_address = address!("0x0000000000000000000000000000000000001111");
+        let storage_keys = [10, 11].map(H256::from_low_u64_be).to_vec();
+        let access_list = AccessList(vec![AccessListItem {
+            address: listed_address,
+            storage_keys: storage_keys.clone(),
+        }]);
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_WALLETS[0].address()).balance(eth(1));
+                accs[1].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            },
+            |mut txs, _accs| {
+                txs[0]
+                    .from(MOCK_WALLETS[0].clone())
+                    .to(MOCK_ACCOUNTS[0])
+                    .gas(40_000.into())
+                    .gas_price(gwei(1))
+                    .transaction_type(1) // EIP-2930
+                    .access_list(access_list);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let begin_tx = &builder.block.txs()[0].steps()[0];
+        assert_eq!(begin_tx.exec_state, ExecState::BeginTx);
+        assert_eq!(
+            begin_tx.gas_cost.as_u64(),
+            GasCost::TX.as_u64()
+                + GasCost::ACCESS_LIST_PER_ADDRESS.as_u64()
+                + 2 * GasCost::ACCESS_LIST_PER_STORAGE_KEY.as_u64()
+        );
+
+        let container = &builder.block.container;
+        let account_op = begin_tx
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::TxAccessListAccount)
+            .map(|op_ref| container.tx_access_list_account[op_ref.1].op())
+            .find(|op| op.address == listed_address)
+            .unwrap();
+        assert!(account_op.is_warm && !account_op.is_warm_prev);
+
+        let storage_ops = begin_tx
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::TxAccessListAccountStorage)
+            .map(|op_ref| container.tx_access_list_account_storage[op_ref.1].op())
+            .map(|op| (op.address, op.key, op.is_warm, op.is_warm_prev))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            storage_ops,
+            storage_keys
+                .iter()
+                .map(|key| (listed_address, key.to_word(), true, false))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn begin_tx_reads_l1_gas_price_oracle() {
+        let oracle_storage = [
+            (
+                *l1_gas_price_oracle::BASE_FEE_SLOT,
+                Word::from(1_000_000_000u64),
+            ),
+            (*l1_gas_price_oracle::OVERHEAD_SLOT, Word::from(2_500u64)),
+            (
+                *l1_gas_price_oracle::SCALAR_SLOT,
+                Word::from(1_150_000_000u64),
+            ),
+        ];
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_WALLETS[0].address()).balance(eth(1));
+                accs[1].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+                accs[2]
+                    .address(*l1_gas_price_oracle::ADDRESS)
+                    .storage(oracle_storage.into_iter());
+            },
+            |mut txs, _accs| {
+                txs[0]
+                    .from(MOCK_WALLETS[0].clone())
+                    .to(MOCK_ACCOUNTS[0])
+                    .gas(30_000.into())
+                    .gas_price(gwei(1));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let begin_tx = &tx.steps()[0];
+        assert_eq!(begin_tx.exec_state, ExecState::BeginTx);
+
+        let container = &builder.block.container;
+        let storage_reads = begin_tx
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::Storage)
+            .map(|op_ref| &container.storage[op_ref.as_usize()])
+            .map(|op| {
+                assert_eq!(op.rw(), RW::READ);
+                assert_eq!(op.op().address, *l1_gas_price_oracle::ADDRESS);
+                (op.op().key, op.op().value)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(storage_reads, oracle_storage);
+
+        let l1_fee = begin_tx
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::CallContext)
+            .map(|op_ref| &container.call_context[op_ref.as_usize()])
+            .find(|op| op.op().field == CallContextField::L1Fee)
+            .unwrap();
+        assert_eq!(l1_fee.rw(), RW::WRITE);
+        assert!(tx.l1_fee() > 0);
+        assert_eq!(l1_fee.op().value, Word::from(tx.l1_fee()));
+    }
+
+    #[test]
+    fn eip1559_tx_pays_effective_gas_price() {
+        let gas = 30_000u64;
+        let value = gwei(20_000);
+        let base_fee = gwei(3);
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_WALLETS[0].address()).balance(eth(1));
+                accs[1].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            },
+            |mut txs, _accs| {
+                txs[0]
+                    .from(MOCK_WALLETS[0].clone())
+                    .to(MOCK_ACCOUNTS[0])
+                    .gas(gas.into())
+                    .value(value)
+                    .max_fee_per_gas(gwei(4))
+                    .max_priority_fee_per_gas(gwei(2))
+                    .transaction_type(2); // EIP-1559
+            },
+            |block, _tx| block.number(0xcafeu64).base_fee_per_gas(base_fee),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        // The tip is capped by `max_fee_per_gas - base_fee`.
+        let tx = &builder.block.txs()[0];
+        let gas_price = tx.effective_gas_price(base_fee);
+        assert_eq!(gas_price, gwei(4));
+        assert_eq!(tx.gas_price, gas_price);
+
+        let sender_balance_diff = |step: &ExecStep| {
+            let op = step
+                .bus_mapping_instance
+                .iter()
+                .filter(|op_ref| op_ref.0 == Target::Account)
+                .map(|op_ref| builder.block.container.account[op_ref.1].op())
+                .find(|op| {
+                    op.address == MOCK_WALLETS[0].address() && op.field == AccountField::Balance
+                })
+                .unwrap();
+            (op.value_prev, op.value)
+        };
+
+        let (prev, value_after) = sender_balance_diff(&tx.steps()[0]);
+        let l1_fee = Word::from(block.geth_traces[0].l1_fee);
+        assert_eq!(prev - value_after, value + gas_price * gas + l1_fee);
+
+        let end_tx = tx.steps().last().unwrap();
+        assert_eq!(end_tx.exec_state, ExecState::EndTx);
+        let (prev, value_after) = sender_balance_diff(end_tx);
+        assert_eq!(value_after - prev, gas_price * (gas - GasCost::TX.as_u64()));
+    }
+}
@@ -0,0 +1,114 @@
+use super::Opcode;
+use crate::{
+    circuit_input_builder::{CircuitInputStateRef, ExecStep},
+    operation::CallContextField,
+    Error,
+};
+use eth_types::{GethExecStep, ToWord, Word};
+
+/// Placeholder structure used to implement [`Opcode`] trait over it
+/// corresponding to the [`OpcodeId::BLOBHASH`](crate::evm::OpcodeId::BLOBHASH)
+/// `OpcodeId`.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Blobhash;
+
+impl Opcode for Blobhash {
+    fn gen_associated_ops(
+        state: &mut CircuitInputStateRef,
+        geth_steps: &[GethExecStep],
+    ) -> Result<Vec<ExecStep>, Error> {
+        let geth_step = &geth_steps[0];
+        let mut exec_step = state.new_step(geth_step)?;
+
+        let index = state.stack_pop(&mut exec_step)?;
+        #[cfg(feature = "enable-stack")]
+        assert_eq!(index, geth_step.stack.last()?);
+
+        state.call_context_read(
+            &mut exec_step,
+            state.call()?.call_id,
+            CallContextField::TxId,
+            state.tx_ctx.id().into(),
+        )?;
+
+        // An index out of the blob versioned hashes of the tx reads as zero.
+        let blob_hash = usize::try_from(index)
+            .ok()
+            .and_then(|index| state.tx.blob_versioned_hashes.get(index))
+            .map_or(Word::zero(), |hash| hash.to_word());
+        #[cfg(feature = "enable-stack")]
+        assert_eq!(blob_hash, geth_steps[1].stack.last()?);
+        state.stack_push(&mut exec_step, blob_hash)?;
+
+        Ok(vec![exec_step])
+    }
+}
+
+#[cfg(all(test, feature = "cancun"))]
+mod blobhash_tests {
+    use crate::{
+        circuit_input_builder::ExecState,
+        mock::BlockData,
+        operation::{CallContextField, CallContextOp, StackOp, RW},
+    };
+    use eth_types::{
+        bytecode,
+        evm_types::{OpcodeId, StackAddress},
+        geth_types::GethData,
+        Word,
+    };
+    use mock::test_ctx::{helpers::*, TestContext};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn blobhash_of_tx_without_blobs_is_zero() {
+        let code = bytecode! {
+            PUSH1(0x00)
+            BLOBHASH
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::BLOBHASH))
+            .unwrap();
+
+        let call_id = builder.block.txs()[0].calls()[0].call_id;
+        let operation =
+            &builder.block.container.call_context[step.bus_mapping_instance[1].as_usize()];
+        assert_eq!(
+            (operation.rw(), operation.op()),
+            (
+                RW::READ,
+                &CallContextOp {
+                    call_id,
+                    field: CallContextField::TxId,
+                    value: Word::one(),
+                }
+            )
+        );
+        let operation = &builder.block.container.stack[step.bus_mapping_instance[2].as_usize()];
+        assert_eq!(
+            (operation.rw(), operation.op()),
+            (
+                RW::WRITE,
+                &StackOp::new(1, StackAddress::from(1023), Word::zero())
+            )
+        );
+    }
+}
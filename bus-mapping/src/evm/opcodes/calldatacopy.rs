@@ -1,7 +1,10 @@
-use super::Opcode;
+use super::{
+    copyop::{gen_copy_opcode_ops, CopyOpcodeBuilder, CopySource},
+    Opcode,
+};
 use crate::{
     circuit_input_builder::{
-        CircuitInputStateRef, CopyBytes, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
+        CircuitInputStateRef, CopyBytes, CopyDataType, ExecStep, NumberOrHash,
     },
     operation::CallContextField,
     Error,
@@ -16,118 +19,89 @@ impl Opcode for Calldatacopy {
         state: &mut CircuitInputStateRef,
         geth_steps: &[GethExecStep],
     ) -> Result<Vec<ExecStep>, Error> {
-        let geth_step = &geth_steps[0];
-
-        let mut exec_step = state.new_step(geth_step)?;
-        let memory_offset = state.stack_pop(&mut exec_step)?;
-        let data_offset = state.stack_pop(&mut exec_step)?;
-        let length = state.stack_pop(&mut exec_step)?;
-        #[cfg(feature = "enable-stack")]
-        {
-            assert_eq!(memory_offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(data_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(length, geth_step.stack.nth_last(2)?);
-        }
-        if state.call()?.is_root {
-            state.call_context_read(
-                &mut exec_step,
-                state.call()?.call_id,
-                CallContextField::TxId,
-                state.tx_ctx.id().into(),
-            )?;
-            state.call_context_read(
-                &mut exec_step,
-                state.call()?.call_id,
-                CallContextField::CallDataLength,
-                state.call()?.call_data_length.into(),
-            )?;
+        gen_copy_opcode_ops::<Self>(state, geth_steps)
+    }
+}
+
+impl CopyOpcodeBuilder for Calldatacopy {
+    const N_POP: usize = 3;
+
+    fn gen_pre_copy_ops(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        _stack_inputs: &[Word],
+    ) -> Result<(), Error> {
+        let call = state.call()?.clone();
+        let fields = if call.is_root {
+            vec![
+                (CallContextField::TxId, state.tx_ctx.id().into()),
+                (
+                    CallContextField::CallDataLength,
+                    call.call_data_length.into(),
+                ),
+            ]
         } else {
-            state.call_context_read(
-                &mut exec_step,
-                state.call()?.call_id,
-                CallContextField::CallerId,
-                state.call()?.caller_id.into(),
-            )?;
-            state.call_context_read(
-                &mut exec_step,
-                state.call()?.call_id,
-                CallContextField::CallDataLength,
-                state.call()?.call_data_length.into(),
-            )?;
-            state.call_context_read(
-                &mut exec_step,
-                state.call()?.call_id,
-                CallContextField::CallDataOffset,
-                state.call()?.call_data_offset.into(),
-            )?;
+            vec![
+                (CallContextField::CallerId, call.caller_id.into()),
+                (
+                    CallContextField::CallDataLength,
+                    call.call_data_length.into(),
+                ),
+                (
+                    CallContextField::CallDataOffset,
+                    call.call_data_offset.into(),
+                ),
+            ]
         };
-
-        let copy_event = gen_copy_event(state, memory_offset, data_offset, length, &mut exec_step)?;
-        state.push_copy(&mut exec_step, copy_event);
-        Ok(vec![exec_step])
+        for (field, value) in fields {
+            state.call_context_read(exec_step, call.call_id, field, value)?;
+        }
+        Ok(())
     }
-}
 
-fn gen_copy_event(
-    state: &mut CircuitInputStateRef,
-    memory_offset: Word,
-    data_offset: Word,
-    length: Word,
-    exec_step: &mut ExecStep,
-) -> Result<CopyEvent, Error> {
-    let rw_counter_start = state.block_ctx.rwc;
-
-    let (memory_offset, length) = (memory_offset.low_u64(), length.as_u64());
-
-    let call_data_offset = state.call()?.call_data_offset;
-    let call_data_length = state.call()?.call_data_length;
-
-    let src_addr_end = call_data_offset.checked_add(call_data_length).unwrap();
-    let src_addr = u64::try_from(data_offset)
-        .ok()
-        .and_then(|s| s.checked_add(call_data_offset))
-        .unwrap_or(src_addr_end)
-        .min(src_addr_end);
-    let dst_addr = memory_offset;
-
-    if state.call()?.is_root {
-        // fetch pre write bytes to fill 'bytes_write_prev' of CopyBytes
-        let (copy_steps, prev_bytes) =
-            state.gen_copy_steps_for_call_data_root(exec_step, src_addr, dst_addr, length)?;
-
-        let copy_bytes = CopyBytes::new(copy_steps, None, Some(prev_bytes));
-
-        Ok(CopyEvent {
-            src_type: CopyDataType::TxCalldata,
-            src_id: NumberOrHash::Number(state.tx_ctx.id()),
-            src_addr,
-            src_addr_end,
-            dst_type: CopyDataType::Memory,
-            dst_id: NumberOrHash::Number(state.call()?.call_id),
-            dst_addr,
-            log_id: None,
-            rw_counter_start,
-            copy_bytes,
-            access_list: vec![],
-        })
-    } else {
-        let (read_steps, write_steps, prev_bytes) =
-            state.gen_copy_steps_for_call_data_non_root(exec_step, src_addr, dst_addr, length)?;
-
-        Ok(CopyEvent {
-            src_type: CopyDataType::Memory,
-            src_id: NumberOrHash::Number(state.call()?.caller_id),
-            src_addr,
-            src_addr_end,
-            dst_type: CopyDataType::Memory,
-            dst_id: NumberOrHash::Number(state.call()?.call_id),
-            dst_addr,
-            log_id: None,
-            rw_counter_start,
-            //fetch pre read and write bytes of CopyBytes
-            copy_bytes: CopyBytes::new(read_steps, Some(write_steps), Some(prev_bytes)),
-            access_list: vec![],
-        })
+    fn gen_copy_source(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        stack_inputs: &[Word],
+        dst_addr: u64,
+        length: u64,
+    ) -> Result<CopySource, Error> {
+        let data_offset = stack_inputs[1];
+        let call_data_offset = state.call()?.call_data_offset;
+        let call_data_length = state.call()?.call_data_length;
+
+        let src_addr_end = call_data_offset.checked_add(call_data_length).unwrap();
+        let src_addr = u64::try_from(data_offset)
+            .ok()
+            .and_then(|s| s.checked_add(call_data_offset))
+            .unwrap_or(src_addr_end)
+            .min(src_addr_end);
+
+        if state.call()?.is_root {
+            // fetch pre write bytes to fill 'bytes_write_prev' of CopyBytes
+            let (copy_steps, prev_bytes) =
+                state.gen_copy_steps_for_call_data_root(exec_step, src_addr, dst_addr, length)?;
+
+            Ok(CopySource {
+                src_type: CopyDataType::TxCalldata,
+                src_id: NumberOrHash::Number(state.tx_ctx.id()),
+                src_addr,
+                src_addr_end,
+                copy_bytes: CopyBytes::new(copy_steps, None, Some(prev_bytes)),
+            })
+        } else {
+            let (read_steps, write_steps, prev_bytes) = state
+                .gen_copy_steps_for_call_data_non_root(exec_step, src_addr, dst_addr, length)?;
+
+            Ok(CopySource {
+                src_type: CopyDataType::Memory,
+                src_id: NumberOrHash::Number(state.call()?.caller_id),
+                src_addr,
+                src_addr_end,
+                //fetch pre read and write bytes of CopyBytes
+                copy_bytes: CopyBytes::new(read_steps, Some(write_steps), Some(prev_bytes)),
+            })
+        }
     }
 }
 
@@ -465,4 +439,48 @@ mod calldatacopy_tests {
             assert!(!is_code);
         }
     }
+
+    #[test]
+    fn calldatacopy_unaligned_uses_word_writes() {
+        let size = 0x40;
+        let dst_offset = 0x05;
+        let code = bytecode! {
+            .op_calldatacopy(dst_offset, 0x00, size)
+            STOP
+        };
+
+        // Get the execution steps from the external tracer
+        let block: GethData = TestContext::<2, 1>::new_with_logger_config(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0]
+                    .to(accs[0].address)
+                    .from(accs[1].address)
+                    .input(vec![0xff; size].into());
+            },
+            |block, _tx| block,
+            LoggerConfig::default(),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        // 0x40 bytes written at 0x05 span the words at 0x00, 0x20 and 0x40.
+        let memory_ops = &builder.block.container.memory;
+        assert_eq!(
+            memory_ops
+                .iter()
+                .map(|op| (op.rw(), op.op().address().0))
+                .collect::<Vec<_>>(),
+            vec![(RW::WRITE, 0x00), (RW::WRITE, 0x20), (RW::WRITE, 0x40)]
+        );
+        let copy_event = &builder.block.copy_events[0];
+        assert_eq!(copy_event.full_length(), 3 * 32);
+        assert_eq!(copy_event.rw_counter_delta(), 3);
+    }
 }
@@ -668,6 +668,22 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
 pub mod tests {
 
     use eth_types::{evm_types::OpcodeId, Bytecode, Word};
+    // Only used by the tests below, the module is also built with the `test`
+    // feature.
+    #[cfg(test)]
+    use crate::{circuit_input_builder::ExecState, mock::BlockData};
+    #[cfg(test)]
+    use eth_types::{
+        address, bytecode,
+        evm_types::{GasCost, GAS_STIPEND_CALL_WITH_VALUE},
+        geth_types::GethData,
+        ToWord,
+    };
+    #[cfg(test)]
+    use mock::{
+        test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
+        TestContext,
+    };
 
     /// Precompile call args
     pub struct PrecompileCallArgs {
@@ -1076,4 +1092,49 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_call_with_value_to_new_account() {
+        let new_address = address!("0x00000000000000000000000000000000000beef1");
+        let code = bytecode! {
+            PUSH1(0) // retSize
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsSize
+            PUSH1(0) // argsOffset
+            PUSH1(1) // value
+            PUSH20(new_address.to_word())
+            PUSH2(0xFFFF) // gas
+            CALL
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let steps = builder.block.txs()[0].steps();
+        let call_index = steps
+            .iter()
+            .position(|step| step.exec_state == ExecState::Op(OpcodeId::CALL))
+            .unwrap();
+        let (call_step, next_step) = (&steps[call_index], &steps[call_index + 1]);
+        // The callee has no code, so all forwarded gas and the stipend come
+        // back to the caller and only the call cost itself is consumed.
+        assert_eq!(
+            call_step.gas_left.0 + GAS_STIPEND_CALL_WITH_VALUE - next_step.gas_left.0,
+            GasCost::COLD_ACCOUNT_ACCESS.as_u64()
+                + GasCost::CALL_WITH_VALUE.as_u64()
+                + GasCost::NEW_ACCOUNT.as_u64()
+        );
+        assert_eq!(builder.sdb.get_balance(&new_address), Word::one());
+    }
 }
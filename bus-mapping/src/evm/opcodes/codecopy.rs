@@ -1,12 +1,15 @@
 use crate::{
     circuit_input_builder::{
-        CircuitInputStateRef, CopyBytes, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
+        CircuitInputStateRef, CopyBytes, CopyDataType, ExecStep, NumberOrHash,
     },
     Error,
 };
 use eth_types::{Bytecode, GethExecStep, Word};
 
-use super::Opcode;
+use super::{
+    copyop::{gen_copy_opcode_ops, CopyOpcodeBuilder, CopySource},
+    Opcode,
+};
 
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct Codecopy;
@@ -16,72 +19,44 @@ impl Opcode for Codecopy {
         state: &mut CircuitInputStateRef,
         geth_steps: &[GethExecStep],
     ) -> Result<Vec<ExecStep>, Error> {
-        let geth_step = &geth_steps[0];
-        let mut exec_step = state.new_step(geth_step)?;
-
-        let dest_offset = state.stack_pop(&mut exec_step)?;
-        let code_offset = state.stack_pop(&mut exec_step)?;
-        let length = state.stack_pop(&mut exec_step)?;
-
-        #[cfg(feature = "enable-stack")]
-        {
-            assert_eq!(dest_offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(code_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(length, geth_step.stack.nth_last(2)?);
-        }
-
-        let copy_event = gen_copy_event(
-            state,
-            dest_offset,
-            code_offset,
-            length.as_u64(),
-            &mut exec_step,
-        )?;
-        state.push_copy(&mut exec_step, copy_event);
-        Ok(vec![exec_step])
+        gen_copy_opcode_ops::<Self>(state, geth_steps)
     }
 }
 
-fn gen_copy_event(
-    state: &mut CircuitInputStateRef,
-    dest_offset: Word,
-    code_offset: Word,
-    length: u64,
-    exec_step: &mut ExecStep,
-) -> Result<CopyEvent, Error> {
-    let rw_counter_start = state.block_ctx.rwc;
-
-    let code_hash = state.call()?.code_hash;
-    let bytecode: Bytecode = state.code(code_hash)?.into();
-    let code_size = bytecode.code.len() as u64;
-
-    // Get low Uint64 of offset.
-    let dst_addr = dest_offset.low_u64();
-    let src_addr_end = code_size;
-
-    // Reset offset to Uint64 maximum value if overflow, and set source start to the
-    // minimum value of offset and code size.
-    let src_addr = u64::try_from(code_offset)
-        .unwrap_or(u64::MAX)
-        .min(src_addr_end);
-
-    let (copy_steps, prev_bytes) =
-        state.gen_copy_steps_for_bytecode(exec_step, &bytecode, src_addr, dst_addr, length)?;
-
-    Ok(CopyEvent {
-        src_type: CopyDataType::Bytecode,
-        src_id: NumberOrHash::Hash(code_hash),
-        src_addr,
-        src_addr_end,
-        dst_type: CopyDataType::Memory,
-        dst_id: NumberOrHash::Number(state.call()?.call_id),
-        dst_addr,
-        log_id: None,
-        rw_counter_start,
-        //fetch pre write bytes of CopyBytes
-        copy_bytes: CopyBytes::new(copy_steps, None, Some(prev_bytes)),
-        access_list: vec![],
-    })
+impl CopyOpcodeBuilder for Codecopy {
+    const N_POP: usize = 3;
+
+    fn gen_copy_source(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        stack_inputs: &[Word],
+        dst_addr: u64,
+        length: u64,
+    ) -> Result<CopySource, Error> {
+        let code_offset = stack_inputs[1];
+        let code_hash = state.call()?.code_hash;
+        let bytecode: Bytecode = state.code(code_hash)?.into();
+        let code_size = bytecode.code.len() as u64;
+
+        let src_addr_end = code_size;
+        // Reset offset to Uint64 maximum value if overflow, and set source start to the
+        // minimum value of offset and code size.
+        let src_addr = u64::try_from(code_offset)
+            .unwrap_or(u64::MAX)
+            .min(src_addr_end);
+
+        let (copy_steps, prev_bytes) =
+            state.gen_copy_steps_for_bytecode(exec_step, &bytecode, src_addr, dst_addr, length)?;
+
+        Ok(CopySource {
+            src_type: CopyDataType::Bytecode,
+            src_id: NumberOrHash::Hash(code_hash),
+            src_addr,
+            src_addr_end,
+            //fetch pre write bytes of CopyBytes
+            copy_bytes: CopyBytes::new(copy_steps, None, Some(prev_bytes)),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,93 @@
+use crate::{
+    circuit_input_builder::{
+        CircuitInputStateRef, CopyBytes, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
+    },
+    Error,
+};
+use eth_types::{GethExecStep, Word};
+
+/// Source of the bytes that a [`CopyOpcodeBuilder`] copies into the memory of
+/// the current call, together with the generated copy steps.
+#[derive(Debug)]
+pub(crate) struct CopySource {
+    pub(crate) src_type: CopyDataType,
+    pub(crate) src_id: NumberOrHash,
+    pub(crate) src_addr: u64,
+    pub(crate) src_addr_end: u64,
+    pub(crate) copy_bytes: CopyBytes,
+}
+
+/// Opcodes copying a slice of some source into the memory of the current
+/// call: CALLDATACOPY, CODECOPY, EXTCODECOPY, RETURNDATACOPY and MCOPY.
+///
+/// They all pop their stack inputs, do some opcode specific reads, and then
+/// emit a single [`CopyEvent`] with the memory of the current call as
+/// destination, see [`gen_copy_opcode_ops`].
+pub(crate) trait CopyOpcodeBuilder {
+    /// Number of stack inputs, the last one being the copy length.
+    const N_POP: usize;
+    /// Index of the destination memory offset in the stack inputs.
+    const DST_OFFSET_INDEX: usize = 0;
+
+    /// Generate the operations done after popping the stack inputs and before
+    /// copying.
+    fn gen_pre_copy_ops(
+        _state: &mut CircuitInputStateRef,
+        _exec_step: &mut ExecStep,
+        _stack_inputs: &[Word],
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Generate the copy steps of `length` bytes to `dst_addr` and return the
+    /// source they are read from.
+    fn gen_copy_source(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        stack_inputs: &[Word],
+        dst_addr: u64,
+        length: u64,
+    ) -> Result<CopySource, Error>;
+}
+
+/// Generate the [`ExecStep`] of a successful copy opcode.
+pub(crate) fn gen_copy_opcode_ops<T: CopyOpcodeBuilder>(
+    state: &mut CircuitInputStateRef,
+    geth_steps: &[GethExecStep],
+) -> Result<Vec<ExecStep>, Error> {
+    let geth_step = &geth_steps[0];
+    let mut exec_step = state.new_step(geth_step)?;
+
+    let stack_inputs = state.stack_pops(&mut exec_step, T::N_POP)?;
+    #[cfg(feature = "enable-stack")]
+    for (i, value) in stack_inputs.iter().enumerate() {
+        assert_eq!(*value, geth_step.stack.nth_last(i)?);
+    }
+
+    T::gen_pre_copy_ops(state, &mut exec_step, &stack_inputs)?;
+
+    let rw_counter_start = state.block_ctx.rwc;
+    // Get low Uint64 of the destination offset, it is only used if the length
+    // is not zero.
+    let dst_addr = stack_inputs[T::DST_OFFSET_INDEX].low_u64();
+    let length = stack_inputs[T::N_POP - 1].as_u64();
+    let source = T::gen_copy_source(state, &mut exec_step, &stack_inputs, dst_addr, length)?;
+
+    let copy_event = CopyEvent {
+        src_type: source.src_type,
+        src_id: source.src_id,
+        src_addr: source.src_addr,
+        src_addr_end: source.src_addr_end,
+        dst_type: CopyDataType::Memory,
+        dst_id: NumberOrHash::Number(state.call()?.call_id),
+        dst_addr,
+        log_id: None,
+        rw_counter_start,
+        copy_bytes: source.copy_bytes,
+        access_list: vec![],
+    };
+    state.push_copy(&mut exec_step, copy_event);
+    #[cfg(feature = "enable-memory")]
+    state.take_trace_memory(geth_steps.get(1))?;
+    Ok(vec![exec_step])
+}
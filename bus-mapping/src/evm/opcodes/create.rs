@@ -10,7 +10,10 @@ use crate::{
 };
 use eth_types::{
     bytecode::BytecodeElement,
-    evm_types::{memory::MemoryWordRange, Memory},
+    evm_types::{
+        gas_utils::memory_expansion_gas_cost, memory::MemoryWordRange, GasCost, Memory,
+        CREATE2_GAS_PER_CODE_WORD, CREATE_GAS_PER_CODE_WORD,
+    },
     state_db::CodeDB,
     Bytecode, GethExecStep, ToBigEndian, ToWord, Word, H160, H256,
 };
@@ -96,6 +99,19 @@ impl<const IS_CREATE2: bool> Opcode for Create<IS_CREATE2> {
                 .extend_at_least(offset + length);
         }
         let next_memory_word_size = state.call_ctx()?.memory_word_size();
+        let curr_memory_word_size = (exec_step.memory_size as u64) / 32;
+        let gas_cost = create_gas_cost(
+            IS_CREATE2,
+            memory_expansion_gas_cost(curr_memory_word_size, next_memory_word_size),
+            length as u64,
+        );
+        if geth_step.gas_cost.as_u64() != gas_cost {
+            log::error!(
+                "create gas cost mismatch, trace {} expected {gas_cost}, init code length {length}",
+                geth_step.gas_cost.as_u64()
+            );
+            return Err(Error::InvalidGethExecTrace("create gas cost mismatch"));
+        }
 
         state.call_context_read(
             &mut exec_step,
@@ -289,6 +305,8 @@ impl<const IS_CREATE2: bool> Opcode for Create<IS_CREATE2> {
                     value_prev: 0.into(),
                 },
             )?;
+            // EIP 6780, a contract created in this tx can be destroyed in it
+            state.tx_created_account_write(&mut exec_step, callee.address)?;
 
             if length > 0 {
                 for (field, value) in [
@@ -345,6 +363,19 @@ impl<const IS_CREATE2: bool> Opcode for Create<IS_CREATE2> {
     }
 }
 
+/// Gas cost of CREATE/CREATE2 without the gas forwarded to the callee, which
+/// includes the EIP-3860 per word cost of the init code.
+fn create_gas_cost(is_create2: bool, memory_expansion_gas_cost: u64, init_code_len: u64) -> u64 {
+    let gas_per_code_word = if is_create2 {
+        CREATE2_GAS_PER_CODE_WORD
+    } else {
+        CREATE_GAS_PER_CODE_WORD
+    };
+    GasCost::CREATE.as_u64()
+        + memory_expansion_gas_cost
+        + (init_code_len + 31) / 32 * gas_per_code_word
+}
+
 fn handle_copy(
     state: &mut CircuitInputStateRef,
     step: &mut ExecStep,
@@ -408,11 +439,21 @@ fn handle_copy(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{circuit_input_builder::ExecState, mock::BlockData, operation::RW};
-    use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData, word};
+    use crate::{
+        circuit_input_builder::ExecState,
+        error::OogError,
+        mock::BlockData,
+        operation::{Target, RW},
+    };
+    use eth_types::{
+        bytecode,
+        evm_types::{OpcodeId, MAX_INIT_CODE_SIZE},
+        geth_types::GethData,
+        word,
+    };
     use mock::{
         test_ctx::{helpers::account_0_code_account_1_no_code, LoggerConfig},
-        TestContext,
+        TestContext, MOCK_ACCOUNTS,
     };
 
     #[test]
@@ -483,4 +524,295 @@ mod tests {
         let operation = &container.stack[step.bus_mapping_instance[5].as_usize()];
         assert_eq!(operation.rw(), RW::READ);
     }
+
+    #[test]
+    fn test_create2_address_collision_step() {
+        let initcode = hex::decode("6B6020600060003760206000F3600052600C6014F3").unwrap();
+        let salt = Word::from(0xef);
+        let code = bytecode! {
+            PUSH21(Word::from_big_endian(&initcode))
+            PUSH1(0)
+            MSTORE
+
+            PUSH32(salt) // salt
+            PUSH1 (0x15) // size
+            PUSH1 (0xB) // offset
+            PUSH1 (0)   // value
+            CREATE2
+
+            PUSH32(salt) // salt
+            PUSH1 (0x15) // size
+            PUSH1 (0xB) // offset
+            PUSH1 (0)   // value
+            CREATE2
+            STOP
+        };
+        let builder = build_create(code);
+        let address = get_create2_address(MOCK_ACCOUNTS[0], salt.to_be_bytes(), initcode);
+
+        let steps = builder.block.txs()[0].steps();
+        let (index, step) = steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| step.exec_state == ExecState::Op(OpcodeId::CREATE2))
+            .last()
+            .unwrap();
+        assert_eq!(
+            step.error,
+            Some(ExecError::ContractAddressCollision(
+                ContractAddressCollisionError::Create2
+            ))
+        );
+
+        let container = &builder.block.container;
+        let stack_push = step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::Stack)
+            .map(|op_ref| &container.stack[op_ref.as_usize()])
+            .find(|op| op.rw() == RW::WRITE)
+            .unwrap();
+        assert_eq!(stack_push.op().value, Word::zero());
+
+        // The caller nonce is increased and the existing callee is read, but
+        // nothing is transferred nor written to the callee.
+        let account_ops = step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::Account)
+            .map(|op_ref| &container.account[op_ref.as_usize()])
+            .collect::<Vec<_>>();
+        let caller_nonce = account_ops
+            .iter()
+            .find(|op| {
+                op.rw() == RW::WRITE
+                    && op.op().address == MOCK_ACCOUNTS[0]
+                    && op.op().field == AccountField::Nonce
+            })
+            .unwrap();
+        assert_eq!(caller_nonce.op().value, caller_nonce.op().value_prev + 1);
+        let callee_nonce = account_ops
+            .iter()
+            .find(|op| op.op().address == address && op.op().field == AccountField::Nonce)
+            .unwrap();
+        assert_eq!(callee_nonce.rw(), RW::READ);
+        assert_eq!(callee_nonce.op().value, Word::one());
+        assert!(account_ops
+            .iter()
+            .all(|op| op.op().address != address || op.rw() == RW::READ));
+
+        // The callee is never entered, its gas is consumed and the caller only
+        // keeps one 64th of the gas left after the CREATE2 cost.
+        let caller_gas_left = (step.gas_left.0 - step.gas_cost.0) / 64;
+        let call_context_ops = step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::CallContext)
+            .map(|op_ref| container.call_context[op_ref.as_usize()].op())
+            .collect::<Vec<_>>();
+        for (field, value) in [
+            (CallContextField::GasLeft, caller_gas_left.into()),
+            (CallContextField::LastCalleeReturnDataOffset, Word::zero()),
+            (CallContextField::LastCalleeReturnDataLength, Word::zero()),
+        ] {
+            assert_eq!(
+                call_context_ops
+                    .iter()
+                    .find(|op| op.field == field)
+                    .unwrap()
+                    .value,
+                value
+            );
+        }
+        assert_eq!(steps[index + 1].gas_left.0, caller_gas_left);
+        assert_eq!(steps[index + 1].call_index, step.call_index);
+    }
+
+    #[test]
+    fn test_create2_address_and_keccak_inputs() {
+        let initcode = hex::decode("6B6020600060003760206000F3600052600C6014F3").unwrap();
+        let salt = Word::from(0xef);
+        let code = bytecode! {
+            PUSH21(Word::from_big_endian(&initcode))
+            PUSH1(0)
+            MSTORE
+
+            PUSH32(salt) // salt
+            PUSH1 (0x15) // size
+            PUSH1 (0xB) // offset
+            PUSH1 (0)   // value
+            CREATE2
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let creator = MOCK_ACCOUNTS[0];
+        let expected_address = get_create2_address(creator, salt.to_be_bytes(), initcode.clone());
+        assert_eq!(builder.block.txs()[0].calls()[1].address, expected_address);
+
+        let keccak_input = std::iter::once(0xffu8)
+            .chain(creator.to_fixed_bytes())
+            .chain(salt.to_be_bytes())
+            .chain(keccak256(&initcode))
+            .collect::<Vec<_>>();
+        assert!(builder.block.sha3_inputs.contains(&keccak_input));
+        assert!(builder.block.sha3_inputs.contains(&initcode));
+    }
+
+    fn build_create(code: Bytecode) -> crate::circuit_input_builder::CircuitInputBuilder {
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        builder
+    }
+
+    #[test]
+    fn test_create_init_code_word_gas() {
+        // 2 words of (zero) init code, expanding memory from 0 to 2 words.
+        let memory_expansion_gas_cost = 2 * 3;
+        for (code, opcode, gas_per_code_word) in [
+            (
+                bytecode! {
+                    PUSH1(0x40) // size
+                    PUSH1(0x00) // offset
+                    PUSH1(0x00) // value
+                    CREATE
+                    STOP
+                },
+                OpcodeId::CREATE,
+                CREATE_GAS_PER_CODE_WORD,
+            ),
+            (
+                bytecode! {
+                    PUSH1(0xef) // salt
+                    PUSH1(0x40) // size
+                    PUSH1(0x00) // offset
+                    PUSH1(0x00) // value
+                    CREATE2
+                    STOP
+                },
+                OpcodeId::CREATE2,
+                CREATE2_GAS_PER_CODE_WORD,
+            ),
+        ] {
+            let builder = build_create(code);
+            let step = builder.block.txs()[0]
+                .steps()
+                .iter()
+                .find(|step| step.exec_state == ExecState::Op(opcode))
+                .unwrap();
+            assert_eq!(
+                step.gas_cost.as_u64(),
+                GasCost::CREATE.as_u64() + memory_expansion_gas_cost + 2 * gas_per_code_word
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_gas_cost_mismatch_is_rejected() {
+        let code = bytecode! {
+            PUSH1(0x40) // size
+            PUSH1(0x00) // offset
+            PUSH1(0x00) // value
+            CREATE
+            STOP
+        };
+        let mut block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let step = block.geth_traces[0]
+            .struct_logs
+            .iter_mut()
+            .find(|step| step.op == OpcodeId::CREATE)
+            .unwrap();
+        step.gas_cost.0 += 1;
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        assert!(matches!(
+            builder.handle_block(&block.eth_block, &block.geth_traces),
+            Err(Error::InvalidGethExecTrace("create gas cost mismatch"))
+        ));
+    }
+
+    #[test]
+    fn test_create2_init_code_at_size_cap() {
+        let code = bytecode! {
+            PUSH1(0xef) // salt
+            PUSH32(MAX_INIT_CODE_SIZE) // size
+            PUSH1(0x00) // offset
+            PUSH1(0x00) // value
+            CREATE2
+            STOP
+        };
+        let builder = build_create(code);
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::CREATE2))
+            .unwrap();
+        assert_eq!(step.error, None);
+
+        let words = MAX_INIT_CODE_SIZE / 32;
+        let memory_expansion_gas_cost = words * GasCost::MEMORY_EXPANSION_LINEAR_COEFF.as_u64()
+            + words * words / GasCost::MEMORY_EXPANSION_QUAD_DENOMINATOR.as_u64();
+        assert_eq!(
+            step.gas_cost.as_u64(),
+            GasCost::CREATE.as_u64()
+                + memory_expansion_gas_cost
+                + words * CREATE2_GAS_PER_CODE_WORD
+        );
+    }
+
+    #[test]
+    fn test_create_init_code_size_cap() {
+        let code = bytecode! {
+            PUSH32(MAX_INIT_CODE_SIZE + 1) // size
+            PUSH1(0x00) // offset
+            PUSH1(0x00) // value
+            CREATE
+            STOP
+        };
+        let builder = build_create(code);
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::CREATE))
+            .unwrap();
+        assert_eq!(step.error, Some(ExecError::OutOfGas(OogError::Create)));
+    }
 }
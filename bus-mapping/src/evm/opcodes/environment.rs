@@ -54,6 +54,12 @@ impl BlockHeaderToField for GetBlockHeaderField<{ OpcodeId::BASEFEE }> {
     }
 }
 
+impl BlockHeaderToField for GetBlockHeaderField<{ OpcodeId::BLOBBASEFEE }> {
+    fn handle(block_head: &BlockHead) -> Word {
+        block_head.blob_base_fee
+    }
+}
+
 impl<const OP: OpcodeId> Opcode for GetBlockHeaderField<OP>
 where
     Self: BlockHeaderToField,
@@ -134,11 +140,17 @@ impl Opcode for Gas {
 
 #[cfg(test)]
 mod tests {
-    use crate::mock::BlockData;
-    use eth_types::{bytecode, geth_types::GethData, Bytecode};
+    use crate::{
+        circuit_input_builder::{CircuitInputBuilder, ExecState},
+        mock::BlockData,
+        operation::RW,
+    };
+    #[cfg(feature = "cancun")]
+    use eth_types::evm_types::block_utils::block_blob_base_fee;
+    use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData, Bytecode, Word};
     use mock::TestContext;
 
-    fn test_trace(code: Bytecode) {
+    fn test_trace(code: Bytecode) -> (GethData, CircuitInputBuilder) {
         // Get the execution steps from the external tracer
         let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
             .unwrap()
@@ -148,6 +160,19 @@ mod tests {
         builder
             .handle_block(&block.eth_block, &block.geth_traces)
             .unwrap();
+        (block, builder)
+    }
+
+    /// Returns the value pushed onto the stack by the first `op` step.
+    fn pushed_value(builder: &CircuitInputBuilder, op: OpcodeId) -> Word {
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(op))
+            .unwrap();
+        let stack_op = &builder.block.container.stack[step.bus_mapping_instance[0].as_usize()];
+        assert_eq!(stack_op.rw(), RW::WRITE);
+        *stack_op.op().value()
     }
 
     #[test]
@@ -166,6 +191,44 @@ mod tests {
         });
     }
 
+    #[test]
+    fn gas_limit_pushes_header_gas_limit() {
+        let (block, builder) = test_trace(bytecode! {
+            GASLIMIT
+            STOP
+        });
+        assert_eq!(
+            pushed_value(&builder, OpcodeId::GASLIMIT),
+            block.eth_block.gas_limit
+        );
+    }
+
+    #[test]
+    fn chain_id_pushes_configured_chain_id() {
+        let (block, builder) = test_trace(bytecode! {
+            CHAINID
+            STOP
+        });
+        assert_eq!(builder.block.chain_id(), block.chain_id);
+        assert_eq!(
+            pushed_value(&builder, OpcodeId::CHAINID),
+            block.chain_id.into()
+        );
+    }
+
+    #[cfg(feature = "cancun")]
+    #[test]
+    fn blob_base_fee_pushes_header_blob_base_fee() {
+        let (block, builder) = test_trace(bytecode! {
+            BLOBBASEFEE
+            STOP
+        });
+        assert_eq!(
+            pushed_value(&builder, OpcodeId::BLOBBASEFEE),
+            block_blob_base_fee(&block.eth_block)
+        );
+    }
+
     #[test]
     fn basefee_opcode_impl() {
         test_trace(bytecode! {
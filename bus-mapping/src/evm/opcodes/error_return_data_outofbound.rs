@@ -71,11 +71,15 @@ impl Opcode for ErrorReturnDataOutOfBound {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{circuit_input_builder::ExecState, mock::BlockData, operation::RW};
-    use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData};
+    use crate::{
+        circuit_input_builder::ExecState,
+        mock::BlockData,
+        operation::{CallContextOp, RW},
+    };
+    use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData, ToWord};
     use mock::{
         test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
-        TestContext, MOCK_DEPLOYED_CONTRACT_BYTECODE,
+        TestContext, MOCK_ACCOUNTS, MOCK_DEPLOYED_CONTRACT_BYTECODE,
     };
 
     #[test]
@@ -137,4 +141,75 @@ mod tests {
         let operation = &container.stack[step.bus_mapping_instance[0].as_usize()];
         assert_eq!(operation.rw(), RW::READ);
     }
+
+    #[test]
+    fn test_returndata_error_after_revert() {
+        let callee_code = bytecode! {
+            PUSH1(0x04) // size
+            PUSH1(0x00) // offset
+            REVERT
+        };
+        let code = bytecode! {
+            PUSH1(0x00) // retLength
+            PUSH1(0x00) // retOffset
+            PUSH1(0x00) // argsLength
+            PUSH1(0x00) // argsOffset
+            PUSH1(0x00) // value
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            PUSH2(0xFFFF) // gas
+            CALL
+            POP
+
+            PUSH1(0x05) // 0x05 > 0x04 (reverted with), result in ReturnDataOutOfBounds
+            PUSH1(0x00)
+            PUSH1(0x00)
+            RETURNDATACOPY
+
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+                accs[2].address(MOCK_ACCOUNTS[2]).code(callee_code);
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let transaction = &builder.block.txs()[0];
+        let step = transaction
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATACOPY))
+            .unwrap();
+        assert_eq!(step.error, Some(ExecError::ReturnDataOutOfBounds));
+
+        let operation =
+            &builder.block.container.call_context[step.bus_mapping_instance[3].as_usize()];
+        assert_eq!(
+            (operation.rw(), operation.op()),
+            (
+                RW::READ,
+                &CallContextOp {
+                    call_id: transaction.calls()[0].call_id,
+                    field: CallContextField::LastCalleeReturnDataLength,
+                    value: Word::from(4),
+                }
+            )
+        );
+    }
 }
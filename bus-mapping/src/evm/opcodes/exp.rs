@@ -68,9 +68,11 @@ impl Opcode for Exponentiation {
 
 #[cfg(test)]
 mod tests {
-    use eth_types::U256;
+    use eth_types::{bytecode, geth_types::GethData, U256};
+    use mock::TestContext;
 
     use super::exp_by_squaring;
+    use crate::mock::BlockData;
 
     #[test]
     fn test_exp_by_squaring() {
@@ -98,4 +100,36 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_repeated_exp_events() {
+        let code = bytecode! {
+            PUSH1(13)
+            PUSH1(3)
+            EXP
+            PUSH1(13)
+            PUSH1(3)
+            EXP
+            PUSH1(3)
+            PUSH1(13)
+            EXP
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+            .unwrap()
+            .into();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let exp_events = &builder.block.exp_events;
+        assert_eq!(exp_events.len(), 2);
+        assert_eq!(
+            (exp_events[0].base, exp_events[0].exponent),
+            (3.into(), 13.into())
+        );
+        assert_eq!(builder.block.exp_event_multiplicity(&exp_events[0]), 2);
+        assert_eq!(builder.block.exp_event_multiplicity(&exp_events[1]), 1);
+    }
 }
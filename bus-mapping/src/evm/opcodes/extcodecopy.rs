@@ -1,12 +1,15 @@
-use super::Opcode;
+use super::{
+    copyop::{gen_copy_opcode_ops, CopyOpcodeBuilder, CopySource},
+    Opcode,
+};
 use crate::{
     circuit_input_builder::{
-        CircuitInputStateRef, CopyBytes, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
+        CircuitInputStateRef, CopyBytes, CopyDataType, ExecStep, NumberOrHash,
     },
     operation::{AccountField, CallContextField, TxAccessListAccountOp},
     Error,
 };
-use eth_types::{Address, Bytecode, GethExecStep, ToAddress, ToWord, Word, H256};
+use eth_types::{Bytecode, GethExecStep, ToAddress, ToWord, Word, H256};
 
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct Extcodecopy;
@@ -16,21 +19,20 @@ impl Opcode for Extcodecopy {
         state: &mut CircuitInputStateRef,
         geth_steps: &[GethExecStep],
     ) -> Result<Vec<ExecStep>, Error> {
-        let geth_step = &geth_steps[0];
-        let mut exec_step = state.new_step(geth_step)?;
-
-        let external_address_word = state.stack_pop(&mut exec_step)?;
-        let external_address = external_address_word.to_address();
-        let dest_offset = state.stack_pop(&mut exec_step)?;
-        let offset = state.stack_pop(&mut exec_step)?;
-        let length = state.stack_pop(&mut exec_step)?;
-        #[cfg(feature = "enable-stack")]
-        {
-            assert_eq!(external_address_word, geth_step.stack.nth_last(0)?);
-            assert_eq!(dest_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(offset, geth_step.stack.nth_last(2)?);
-            assert_eq!(length, geth_step.stack.nth_last(3)?);
-        }
+        gen_copy_opcode_ops::<Self>(state, geth_steps)
+    }
+}
+
+impl CopyOpcodeBuilder for Extcodecopy {
+    const N_POP: usize = 4;
+    const DST_OFFSET_INDEX: usize = 1;
+
+    fn gen_pre_copy_ops(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        stack_inputs: &[Word],
+    ) -> Result<(), Error> {
+        let external_address = stack_inputs[0].to_address();
 
         for (field, value) in [
             (CallContextField::TxId, Word::from(state.tx_ctx.id())),
@@ -43,12 +45,12 @@ impl Opcode for Extcodecopy {
                 Word::from(state.call()?.is_persistent as u64),
             ),
         ] {
-            state.call_context_read(&mut exec_step, state.call()?.call_id, field, value)?;
+            state.call_context_read(exec_step, state.call()?.call_id, field, value)?;
         }
 
         let is_warm = state.sdb.check_account_in_access_list(&external_address);
         state.push_op_reversible(
-            &mut exec_step,
+            exec_step,
             TxAccessListAccountOp {
                 tx_id: state.tx_ctx.id(),
                 address: external_address,
@@ -66,76 +68,56 @@ impl Opcode for Extcodecopy {
         };
 
         state.account_read(
-            &mut exec_step,
+            exec_step,
             external_address,
             AccountField::CodeHash,
             code_hash.to_word(),
-        )?;
-
-        let copy_event = gen_copy_event(
-            state,
-            external_address,
-            dest_offset,
-            offset,
-            length,
-            &mut exec_step,
-        )?;
-        state.push_copy(&mut exec_step, copy_event);
-        Ok(vec![exec_step])
+        )
     }
-}
 
-fn gen_copy_event(
-    state: &mut CircuitInputStateRef,
-    external_address: Address,
-    dest_offset: Word,
-    offset: Word,
-    length: Word,
-    exec_step: &mut ExecStep,
-) -> Result<CopyEvent, Error> {
-    let rw_counter_start = state.block_ctx.rwc;
-
-    let length = length.as_u64();
-
-    let account = state.sdb.get_account(&external_address).1;
-    let exists = !account.is_empty();
-    let code_hash = if exists {
-        account.code_hash
-    } else {
-        H256::zero()
-    };
+    fn gen_copy_source(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        stack_inputs: &[Word],
+        dst_addr: u64,
+        length: u64,
+    ) -> Result<CopySource, Error> {
+        let external_address = stack_inputs[0].to_address();
+        let offset = stack_inputs[2];
 
-    let bytecode: Bytecode = if exists {
-        state.code(code_hash)?.into()
-    } else {
-        Bytecode::default()
-    };
-    let code_size = bytecode.code.len() as u64;
-
-    // Get low Uint64 of offset.
-    let dst_addr = dest_offset.low_u64();
-    let src_addr_end = code_size;
-
-    // Reset offset to Uint64 maximum value if overflow, and set source start to the
-    // minimum value of offset and code size.
-    let src_addr = u64::try_from(offset).unwrap_or(u64::MAX).min(src_addr_end);
-
-    let (copy_steps, prev_bytes) =
-        state.gen_copy_steps_for_bytecode(exec_step, &bytecode, src_addr, dst_addr, length)?;
-
-    Ok(CopyEvent {
-        src_addr,
-        src_addr_end,
-        src_type: CopyDataType::Bytecode,
-        src_id: NumberOrHash::Hash(code_hash),
-        dst_addr,
-        dst_type: CopyDataType::Memory,
-        dst_id: NumberOrHash::Number(state.call()?.call_id),
-        log_id: None,
-        rw_counter_start,
-        copy_bytes: CopyBytes::new(copy_steps, None, Some(prev_bytes)),
-        access_list: vec![],
-    })
+        let account = state.sdb.get_account(&external_address).1;
+        let exists = !account.is_empty();
+        let code_hash = if exists {
+            account.code_hash
+        } else {
+            H256::zero()
+        };
+
+        // Precompiles and EOAs have no code, so the copy is all padding and there
+        // is no need to consult the code db.
+        let bytecode: Bytecode = if exists && !account.code_size.is_zero() {
+            state.code(code_hash)?.into()
+        } else {
+            Bytecode::default()
+        };
+        let code_size = bytecode.code.len() as u64;
+
+        let src_addr_end = code_size;
+        // Reset offset to Uint64 maximum value if overflow, and set source start to the
+        // minimum value of offset and code size.
+        let src_addr = u64::try_from(offset).unwrap_or(u64::MAX).min(src_addr_end);
+
+        let (copy_steps, prev_bytes) =
+            state.gen_copy_steps_for_bytecode(exec_step, &bytecode, src_addr, dst_addr, length)?;
+
+        Ok(CopySource {
+            src_type: CopyDataType::Bytecode,
+            src_id: NumberOrHash::Hash(code_hash),
+            src_addr,
+            src_addr_end,
+            copy_bytes: CopyBytes::new(copy_steps, None, Some(prev_bytes)),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -153,7 +135,7 @@ mod extcodecopy_tests {
         evm_types::{MemoryAddress, OpcodeId, StackAddress},
         geth_types::GethData,
         state_db::CodeDB,
-        Bytecode, Bytes, ToWord, Word, U256,
+        Address, Bytecode, Bytes, ToWord, Word, U256,
     };
     use mock::{test_ctx::LoggerConfig, TestContext};
 
@@ -432,6 +414,74 @@ mod extcodecopy_tests {
         }
     }
 
+    fn test_no_code(external_address: Address, balance: Option<Word>) {
+        let code = bytecode! {
+            PUSH32 (0x40)
+            PUSH32 (0x0)
+            PUSH32 (0x0)
+            PUSH20 (external_address.to_word())
+            EXTCODECOPY
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .code(code.clone());
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000cafe01"))
+                    .balance(Word::from(1u64 << 20));
+                if let Some(balance) = balance {
+                    accs[2].address(external_address).balance(balance);
+                } else {
+                    accs[2].address(address!("0x0000000000000000000000000000000000cafe02"));
+                }
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[1].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let copy_events = &builder.block.copy_events;
+        assert_eq!(copy_events.len(), 1);
+        assert_eq!(copy_events[0].src_addr, 0);
+        assert_eq!(copy_events[0].src_addr_end, 0);
+        assert!(copy_events[0]
+            .copy_bytes
+            .bytes
+            .iter()
+            .all(|(value, _, _)| *value == 0));
+        assert!(builder
+            .block
+            .container
+            .memory
+            .iter()
+            .all(|op| op.op().value().is_zero()));
+    }
+
+    #[test]
+    fn eoa_account_copies_zeros() {
+        test_no_code(
+            address!("0x0000000000000000000000000000000000cafe03"),
+            Some(Word::from(1u64 << 10)),
+        );
+    }
+
+    #[test]
+    fn precompile_account_copies_zeros() {
+        test_no_code(address!("0x0000000000000000000000000000000000000004"), None);
+    }
+
     #[test]
     fn cold_empty_account() {
         test_ok(Bytes::from([]), false, 0x0usize, 0x0usize, 0x30usize);
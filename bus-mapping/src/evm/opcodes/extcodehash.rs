@@ -123,6 +123,92 @@ mod extcodehash_tests {
         test_ok(true, true)
     }
 
+    #[test]
+    fn precompile_and_empty_accounts() {
+        let funded_precompile = address!("0x0000000000000000000000000000000000000002");
+        let precompile = address!("0x0000000000000000000000000000000000000004");
+        let empty = address!("0xaabbccddee000000000000000000000000000000");
+
+        // Zero value CALL to `empty` first, which warms it up without creating
+        // the account.
+        let code = bytecode! {
+            PUSH1(0) // retLength
+            PUSH1(0) // retOffset
+            PUSH1(0) // argsLength
+            PUSH1(0) // argsOffset
+            PUSH1(0) // value
+            PUSH20(empty.to_word())
+            PUSH2(0xffff) // gas
+            CALL
+            POP
+            PUSH20(precompile.to_word())
+            EXTCODEHASH
+            POP
+            PUSH20(funded_precompile.to_word())
+            EXTCODEHASH
+            POP
+            PUSH20(empty.to_word())
+            EXTCODEHASH
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(Word::from(1u64 << 20))
+                    .code(code);
+                accs[1].address(funded_precompile).balance(Word::one());
+                accs[2]
+                    .address(address!("0x0000000000000000000000000000000000cafe01"))
+                    .balance(Word::from(1u64 << 20));
+            },
+            |mut txs, accs| {
+                txs[0].to(accs[0].address).from(accs[2].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let container = &builder.block.container;
+        let steps = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::EXTCODEHASH))
+            .collect::<Vec<_>>();
+        assert_eq!(steps.len(), 3);
+        // Precompiles are warm from the start of the tx, and only accounts with
+        // a balance, nonce or code exist.
+        for (step, (address, code_hash)) in steps.into_iter().zip([
+            (precompile, Word::zero()),
+            (funded_precompile, Word::from(keccak256(Bytes::default()))),
+            (empty, Word::zero()),
+        ]) {
+            let indices = &step.bus_mapping_instance;
+            assert_eq!(
+                container.tx_access_list_account[indices[4].as_usize()].op(),
+                &TxAccessListAccountOp {
+                    tx_id: 1,
+                    address,
+                    is_warm: true,
+                    is_warm_prev: true,
+                }
+            );
+            let operation = &container.account[indices[5].as_usize()];
+            assert_eq!(operation.rw(), RW::READ);
+            assert_eq!(operation.op().address, address);
+            assert_eq!(operation.op().value, code_hash);
+            assert_eq!(container.stack[indices[6].as_usize()].op().value, code_hash);
+        }
+    }
+
     fn test_ok(exists: bool, is_warm: bool) -> Result<(), Error> {
         // In each test case, this is the external address we will call EXTCODEHASH on.
         let external_address = address!("0xaabbccddee000000000000000000000000000000");
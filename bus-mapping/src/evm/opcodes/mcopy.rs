@@ -1,12 +1,15 @@
 use crate::{
     circuit_input_builder::{
-        CircuitInputStateRef, CopyBytes, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
+        CircuitInputStateRef, CopyBytes, CopyDataType, ExecStep, NumberOrHash,
     },
     Error,
 };
 use eth_types::{GethExecStep, Word};
 
-use super::Opcode;
+use super::{
+    copyop::{gen_copy_opcode_ops, CopyOpcodeBuilder, CopySource},
+    Opcode,
+};
 
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct MCopy;
@@ -16,72 +19,42 @@ impl Opcode for MCopy {
         state: &mut CircuitInputStateRef,
         geth_steps: &[GethExecStep],
     ) -> Result<Vec<ExecStep>, Error> {
-        let geth_step = &geth_steps[0];
-        let mut exec_step = state.new_step(geth_step)?;
-
-        let dest_offset = state.stack_pop(&mut exec_step)?;
-        let src_offset = state.stack_pop(&mut exec_step)?;
-        let length = state.stack_pop(&mut exec_step)?;
-
-        #[cfg(feature = "enable-stack")]
-        {
-            assert_eq!(dest_offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(src_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(length, geth_step.stack.nth_last(2)?);
-        }
-
-        let copy_event = gen_copy_event(
-            state,
-            dest_offset,
-            src_offset,
-            length.as_u64(),
-            &mut exec_step,
-        )?;
-        state.push_copy(&mut exec_step, copy_event);
-        Ok(vec![exec_step])
+        gen_copy_opcode_ops::<Self>(state, geth_steps)
     }
 }
 
-fn gen_copy_event(
-    state: &mut CircuitInputStateRef,
-    dest_offset: Word,
-    src_offset: Word,
-    length: u64,
-    exec_step: &mut ExecStep,
-) -> Result<CopyEvent, Error> {
-    let rw_counter_start = state.block_ctx.rwc;
-
-    let call_id = state.call()?.call_id;
-
-    // Get low Uint64 of offset.
-    let dst_addr = dest_offset.low_u64();
-    let src_addr_end = src_offset.low_u64() + length;
-
-    // Reset offset to Uint64 maximum value if overflow, and set source start to the
-    // minimum value of offset.
-    let src_addr = u64::try_from(src_offset)
-        .unwrap_or(u64::MAX)
-        .min(src_addr_end);
-
-    let (read_steps, write_steps, prev_bytes) =
-        state.gen_copy_steps_for_memory_to_memory(exec_step, src_addr, dst_addr, length)?;
-
-    Ok(CopyEvent {
-        src_type: CopyDataType::Memory,
-        // use call_id as src id for memory --> memory type.
-        src_id: NumberOrHash::Number(call_id),
-        src_addr,
-        src_addr_end,
-        dst_type: CopyDataType::Memory,
-        // dst_id is also call_id
-        dst_id: NumberOrHash::Number(call_id),
-        dst_addr,
-        log_id: None,
-        rw_counter_start,
-        // needs both read/write and prev bytes in CopyBytes
-        copy_bytes: CopyBytes::new(read_steps, Some(write_steps), Some(prev_bytes)),
-        access_list: vec![],
-    })
+impl CopyOpcodeBuilder for MCopy {
+    const N_POP: usize = 3;
+
+    fn gen_copy_source(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        stack_inputs: &[Word],
+        dst_addr: u64,
+        length: u64,
+    ) -> Result<CopySource, Error> {
+        let src_offset = stack_inputs[1];
+        let src_addr_end = src_offset.low_u64() + length;
+
+        // Reset offset to Uint64 maximum value if overflow, and set source start to the
+        // minimum value of offset.
+        let src_addr = u64::try_from(src_offset)
+            .unwrap_or(u64::MAX)
+            .min(src_addr_end);
+
+        let (read_steps, write_steps, prev_bytes) =
+            state.gen_copy_steps_for_memory_to_memory(exec_step, src_addr, dst_addr, length)?;
+
+        Ok(CopySource {
+            src_type: CopyDataType::Memory,
+            // use call_id as src id for memory --> memory type, same as the dst id.
+            src_id: NumberOrHash::Number(state.call()?.call_id),
+            src_addr,
+            src_addr_end,
+            // needs both read/write and prev bytes in CopyBytes
+            copy_bytes: CopyBytes::new(read_steps, Some(write_steps), Some(prev_bytes)),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -90,7 +63,7 @@ mod mcopy_tests {
         bytecode,
         evm_types::{MemoryAddress, OpcodeId, StackAddress},
         geth_types::GethData,
-        Word,
+        ToBigEndian, Word,
     };
     use mock::{
         test_ctx::{
@@ -103,7 +76,7 @@ mod mcopy_tests {
     use crate::{
         circuit_input_builder::{CopyDataType, ExecState, NumberOrHash},
         mock::BlockData,
-        operation::{MemoryOp, StackOp, RW},
+        operation::{MemoryOp, StackOp, Target, RW},
     };
 
     #[test]
@@ -117,6 +90,74 @@ mod mcopy_tests {
         test_ok(0x90, 0x200, 0xE9);
     }
 
+    #[test]
+    fn mcopy_overlapping_regions() {
+        // forward copy, destination overlaps the end of the source
+        test_overlap(0x00, 0x04, 0x30);
+        // backward copy, destination overlaps the start of the source
+        test_overlap(0x04, 0x00, 0x30);
+        // word aligned overlap
+        test_overlap(0x00, 0x20, 0x20);
+    }
+
+    /// Checks the memory words written by MCOPY match `copy_within` over the
+    /// initial memory, i.e. the source is read as if copied to a buffer first.
+    fn test_overlap(src_offset: usize, dest_offset: usize, copy_size: usize) {
+        let mut memory: Vec<u8> = (1..=0x40).collect();
+        let code = bytecode! {
+            PUSH32(Word::from_big_endian(&memory[0x20..]))
+            PUSH1(0x20)
+            MSTORE
+            PUSH32(Word::from_big_endian(&memory[..0x20]))
+            PUSH1(0x00)
+            MSTORE
+            PUSH2(copy_size)
+            PUSH2(src_offset)
+            PUSH2(dest_offset)
+            MCOPY
+            STOP
+        };
+        memory.copy_within(src_offset..src_offset + copy_size, dest_offset);
+
+        let block: GethData = TestContext::<2, 1>::new_with_logger_config(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+            LoggerConfig::default(),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::MCOPY))
+            .unwrap();
+        let writes = step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::Memory)
+            .map(|op_ref| &builder.block.container.memory[op_ref.1])
+            .filter(|op| op.rw() == RW::WRITE)
+            .collect::<Vec<_>>();
+        assert!(!writes.is_empty());
+
+        for op in writes {
+            let address = op.op().address().0;
+            assert_eq!(
+                op.op().value().to_be_bytes(),
+                memory[address..address + 32],
+                "memory word at {address:#x}"
+            );
+        }
+    }
+
     fn test_ok(src_offset: usize, dest_offset: usize, copy_size: usize) {
         let code = bytecode! {
             .setup_state()
@@ -111,4 +111,60 @@ mod origin_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn origin_in_nested_call_is_tx_sender() {
+        let callee_code = bytecode! {
+            ORIGIN
+            CALLER
+            STOP
+        };
+        let code = bytecode! {
+            PUSH1(0x00) // retSize
+            PUSH1(0x00) // retOffset
+            PUSH1(0x00) // argsSize
+            PUSH1(0x00) // argsOffset
+            PUSH1(0x00) // value
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            PUSH2(0xFFFF) // gas
+            CALL
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+                accs[2].address(MOCK_ACCOUNTS[2]).code(callee_code);
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let pushed_value = |op| {
+            let step = builder.block.txs()[0]
+                .steps()
+                .iter()
+                .find(|step| step.exec_state == ExecState::Op(op))
+                .unwrap();
+            let stack_op = &builder.block.container.stack[step.bus_mapping_instance[1].as_usize()];
+            assert_eq!(stack_op.rw(), RW::WRITE);
+            *stack_op.op().value()
+        };
+
+        assert_eq!(pushed_value(OpcodeId::ORIGIN), MOCK_ACCOUNTS[1].to_word());
+        assert_eq!(pushed_value(OpcodeId::CALLER), MOCK_ACCOUNTS[0].to_word());
+    }
 }
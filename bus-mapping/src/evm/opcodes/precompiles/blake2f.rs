@@ -0,0 +1,71 @@
+use crate::{
+    circuit_input_builder::{Blake2fOp, PrecompileEvent},
+    precompile::PrecompileAuxData,
+};
+
+pub(crate) fn opt_data(
+    input_bytes: &[u8],
+    output_bytes: &[u8],
+    return_bytes: &[u8],
+) -> (Option<PrecompileEvent>, Option<PrecompileAuxData>) {
+    // The EVM circuit verifies blake2f calls with the base precompile gadget,
+    // the compression itself is left to a dedicated circuit.
+    let aux_data = PrecompileAuxData::Base {
+        input_bytes: input_bytes.to_vec(),
+        output_bytes: output_bytes.to_vec(),
+        return_bytes: return_bytes.to_vec(),
+    };
+    let opt_event = Blake2fOp::new_from_bytes(input_bytes, output_bytes)
+        .map(|op| PrecompileEvent::Blake2F(Box::new(op)));
+
+    (opt_event, Some(aux_data))
+}
+
+#[cfg(test)]
+mod blake2f_tests {
+    use crate::circuit_input_builder::Blake2fOp;
+
+    /// Input of test vector 5 of EIP-152: blake2b("abc") with 12 rounds.
+    fn input() -> Vec<u8> {
+        let h: [u64; 8] = [
+            0x6a09e667f2bdc948,
+            0xbb67ae8584caa73b,
+            0x3c6ef372fe94f82b,
+            0xa54ff53a5f1d36f1,
+            0x510e527fade682d1,
+            0x9b05688c2b3e6c1f,
+            0x1f83d9abfb41bd6b,
+            0x5be0cd19137e2179,
+        ];
+        let mut input = 12u32.to_be_bytes().to_vec();
+        input.extend(h.iter().flat_map(|word| word.to_le_bytes()));
+        input.extend(b"abc");
+        input.resize(196, 0);
+        input.extend(3u64.to_le_bytes());
+        input.extend(0u64.to_le_bytes());
+        input.push(1);
+        input
+    }
+
+    #[test]
+    fn blake2f_op_from_bytes() {
+        let input = input();
+        let output = (0..Blake2fOp::OUTPUT_LEN as u8).collect::<Vec<_>>();
+
+        let op = Blake2fOp::new_from_bytes(&input, &output).unwrap();
+        assert_eq!(op.rounds, 12);
+        assert_eq!(op.h[0], 0x6a09e667f2bdc948);
+        assert_eq!(op.h[7], 0x5be0cd19137e2179);
+        assert_eq!(op.m[0], 0x636261);
+        assert!(op.m[1..].iter().all(|&word| word == 0));
+        assert_eq!(op.t, [3, 0]);
+        assert!(op.f);
+        assert_eq!(op.output[0], 0x0706050403020100);
+
+        // Wrong length or a final block flag other than 0 or 1 fail the call.
+        assert_eq!(Blake2fOp::new_from_bytes(&input[1..], &output), None);
+        let mut bad_flag = input;
+        bad_flag[212] = 2;
+        assert_eq!(Blake2fOp::new_from_bytes(&bad_flag, &output), None);
+    }
+}
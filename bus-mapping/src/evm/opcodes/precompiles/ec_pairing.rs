@@ -98,3 +98,50 @@ pub(crate) fn opt_data(
         )))),
     )
 }
+
+#[cfg(test)]
+mod ec_pairing_tests {
+    use super::opt_data;
+    use crate::{
+        circuit_input_builder::{
+            EcPairingPair, PrecompileEvent, N_BYTES_PER_PAIR, N_PAIRING_PER_OP,
+        },
+        precompile::{EcPairingError, PrecompileAuxData},
+    };
+    use eth_types::{ToBigEndian, U256};
+
+    fn input(n_pairs: usize) -> Vec<u8> {
+        (1..=n_pairs * N_BYTES_PER_PAIR / 32)
+            .flat_map(|word| U256::from(word).to_be_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn ec_pairing_pads_pairs() {
+        let output = U256::one().to_be_bytes();
+        for n_pairs in 1..=N_PAIRING_PER_OP {
+            let (event, _) = opt_data(&input(n_pairs), &output, &output);
+            let Some(PrecompileEvent::EcPairing(op)) = event else {
+                panic!("expected an EcPairing event");
+            };
+            assert_eq!(op.pairs[0].g1_point, (1.into(), 2.into()));
+            assert!(op.pairs[n_pairs..]
+                .iter()
+                .all(|pair| *pair == EcPairingPair::padding_pair()));
+            assert_eq!(op.output, U256::one());
+        }
+    }
+
+    #[test]
+    fn ec_pairing_too_many_pairs() {
+        let input = input(N_PAIRING_PER_OP + 1);
+        let (event, aux_data) = opt_data(&input, &[], &[]);
+        assert!(event.is_none());
+        assert_eq!(
+            aux_data,
+            Some(PrecompileAuxData::EcPairing(Box::new(Err(
+                EcPairingError::InvalidInputLen(input)
+            ))))
+        );
+    }
+}
@@ -9,12 +9,14 @@ use crate::{
     Error,
 };
 
+mod blake2f;
 mod ec_add;
 mod ec_mul;
 mod ec_pairing;
 mod ecrecover;
 mod modexp;
 
+use blake2f::opt_data as opt_data_blake2f;
 use ec_add::opt_data as opt_data_ec_add;
 use ec_mul::opt_data as opt_data_ec_mul;
 use ec_pairing::opt_data as opt_data_ec_pairing;
@@ -66,6 +68,7 @@ pub fn gen_ops(
             opt_data_ec_pairing(input_bytes, output_bytes, return_bytes)
         }
         PrecompileCalls::Modexp => opt_data_modexp(input_bytes, output_bytes, return_bytes),
+        PrecompileCalls::Blake2F => opt_data_blake2f(input_bytes, output_bytes, return_bytes),
         PrecompileCalls::Identity => (
             None,
             Some(PrecompileAuxData::Identity {
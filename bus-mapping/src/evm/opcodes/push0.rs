@@ -21,3 +21,64 @@ impl Opcode for Push0 {
         Ok(vec![exec_step])
     }
 }
+
+#[cfg(test)]
+mod push0_tests {
+    use crate::{
+        circuit_input_builder::ExecState,
+        mock::BlockData,
+        operation::{StackOp, RW},
+    };
+    use eth_types::{
+        bytecode,
+        evm_types::{OpcodeId, StackAddress},
+        geth_types::GethData,
+        Word,
+    };
+    use mock::test_ctx::{helpers::*, TestContext};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn push0_pushes_zero_without_immediate() {
+        let code = bytecode! {
+            PUSH0
+            PUSH1(0x01)
+            STOP
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let steps = builder.block.txs()[0].steps();
+        let index = steps
+            .iter()
+            .position(|step| step.exec_state == ExecState::Op(OpcodeId::PUSH0))
+            .unwrap();
+        let step = &steps[index];
+        assert_eq!(step.bus_mapping_instance.len(), 1);
+        let operation = &builder.block.container.stack[step.bus_mapping_instance[0].as_usize()];
+        assert_eq!(
+            (operation.rw(), operation.op()),
+            (
+                RW::WRITE,
+                &StackOp::new(1, StackAddress::from(1023), Word::zero())
+            )
+        );
+
+        // The next opcode directly follows PUSH0.
+        let next = &steps[index + 1];
+        assert_eq!(next.exec_state, ExecState::Op(OpcodeId::PUSH1));
+        assert_eq!(next.pc.0, step.pc.0 + 1);
+    }
+}
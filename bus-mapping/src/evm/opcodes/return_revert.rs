@@ -216,6 +216,11 @@ impl Opcode for ReturnRevert {
             steps,
             false,
         )?;
+        // the return data copied into the caller memory
+        #[cfg(feature = "enable-memory")]
+        if !call.is_root {
+            state.take_trace_memory(steps.get(1))?;
+        }
         Ok(vec![exec_step])
     }
 }
@@ -374,14 +379,20 @@ fn handle_create(
 
 #[cfg(test)]
 mod return_tests {
-    use crate::mock::BlockData;
-    use eth_types::{bytecode, geth_types::GethData, word};
+    use crate::{
+        circuit_input_builder::ExecState,
+        mock::BlockData,
+        operation::{Target, RW},
+    };
+    use eth_types::{
+        bytecode, evm_types::OpcodeId, geth_types::GethData, word, ToBigEndian, ToWord, Word,
+    };
     use mock::{
         test_ctx::{
             helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
             LoggerConfig,
         },
-        TestContext, MOCK_DEPLOYED_CONTRACT_BYTECODE,
+        TestContext, MOCK_ACCOUNTS, MOCK_DEPLOYED_CONTRACT_BYTECODE,
     };
 
     #[test]
@@ -481,4 +492,79 @@ mod return_tests {
             .handle_block(&block.eth_block, &block.geth_traces)
             .unwrap();
     }
+
+    #[test]
+    fn test_return_into_larger_destination() {
+        const PATTERN: u8 = 0xaa;
+        const RETURN_DATA: [u8; 4] = [0x11, 0x22, 0x33, 0x44];
+
+        let callee_code = bytecode! {
+            PUSH4(Word::from_big_endian(&RETURN_DATA))
+            PUSH1(0x00)
+            MSTORE
+            // return the 4 bytes at memory[0x1c..0x20]
+            PUSH1(0x04)
+            PUSH1(0x1c)
+            RETURN
+        };
+        let code = bytecode! {
+            PUSH32(Word::from_big_endian(&[PATTERN; 32]))
+            PUSH1(0x00)
+            MSTORE
+
+            PUSH1(0x20) // retSize
+            PUSH1(0x00) // retOffset
+            PUSH1(0x00) // argsSize
+            PUSH1(0x00) // argsOffset
+            PUSH1(0x00) // value
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            PUSH2(0xFFFF) // gas
+            CALL
+            STOP
+        };
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+                accs[2].address(MOCK_ACCOUNTS[2]).code(callee_code);
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let caller_id = tx.calls()[0].call_id;
+        let step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURN))
+            .unwrap();
+        let writes = step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::Memory)
+            .map(|op_ref| &builder.block.container.memory[op_ref.1])
+            .filter(|op| op.rw() == RW::WRITE && op.op().call_id() == caller_id)
+            .collect::<Vec<_>>();
+        assert_eq!(writes.len(), 1);
+
+        let op = writes[0].op();
+        assert_eq!(op.address().0, 0);
+        assert_eq!(op.value_prev().to_be_bytes(), [PATTERN; 32]);
+        let value = op.value().to_be_bytes();
+        assert_eq!(value[..4], RETURN_DATA);
+        assert_eq!(value[4..], [PATTERN; 28]);
+    }
 }
@@ -1,8 +1,11 @@
 use crate::{
     circuit_input_builder::{
-        CircuitInputStateRef, CopyBytes, CopyDataType, CopyEvent, ExecStep, NumberOrHash,
+        CircuitInputStateRef, CopyBytes, CopyDataType, ExecStep, NumberOrHash,
+    },
+    evm::{
+        opcodes::copyop::{gen_copy_opcode_ops, CopyOpcodeBuilder, CopySource},
+        Opcode,
     },
-    evm::Opcode,
     operation::CallContextField,
     Error,
 };
@@ -16,19 +19,18 @@ impl Opcode for Returndatacopy {
         state: &mut CircuitInputStateRef,
         geth_steps: &[GethExecStep],
     ) -> Result<Vec<ExecStep>, Error> {
-        let geth_step = &geth_steps[0];
-        let mut exec_step = state.new_step(geth_step)?;
-        let memory_offset = state.stack_pop(&mut exec_step)?;
-        let data_offset = state.stack_pop(&mut exec_step)?;
-        let length = state.stack_pop(&mut exec_step)?;
-
-        #[cfg(feature = "enable-stack")]
-        {
-            assert_eq!(memory_offset, geth_step.stack.nth_last(0)?);
-            assert_eq!(data_offset, geth_step.stack.nth_last(1)?);
-            assert_eq!(length, geth_step.stack.nth_last(2)?);
-        }
+        gen_copy_opcode_ops::<Self>(state, geth_steps)
+    }
+}
 
+impl CopyOpcodeBuilder for Returndatacopy {
+    const N_POP: usize = 3;
+
+    fn gen_pre_copy_ops(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        _stack_inputs: &[Word],
+    ) -> Result<(), Error> {
         let call_id = state.call()?.call_id;
         let call_ctx = state.call_ctx()?;
         let return_data_len = call_ctx.return_data.len();
@@ -57,60 +59,50 @@ impl Opcode for Returndatacopy {
                 return_data_len.into(),
             ),
         ] {
-            state.call_context_read(&mut exec_step, call_id, field, value)?;
+            state.call_context_read(exec_step, call_id, field, value)?;
         }
-
-        let copy_event = gen_copy_event(state, memory_offset, data_offset, length, &mut exec_step)?;
-        state.push_copy(&mut exec_step, copy_event);
-        Ok(vec![exec_step])
+        Ok(())
     }
-}
 
-fn gen_copy_event(
-    state: &mut CircuitInputStateRef,
-    memory_offset: Word,
-    data_offset: Word,
-    length: Word,
-    exec_step: &mut ExecStep,
-) -> Result<CopyEvent, Error> {
-    let rw_counter_start = state.block_ctx.rwc;
-
-    // Get low Uint64 of offset.
-    let (dst_addr, data_offset, length) = (
-        memory_offset.low_u64(),
-        data_offset.as_u64(),
-        length.as_u64(),
-    );
-
-    let last_callee_return_data_offset = state.call()?.last_callee_return_data_offset;
-    let last_callee_return_data_length = state.call()?.last_callee_return_data_length;
-    let (src_addr, src_addr_end) = (
-        last_callee_return_data_offset + data_offset,
-        last_callee_return_data_offset + last_callee_return_data_length,
-    );
-
-    let (read_steps, write_steps, prev_bytes) =
-        state.gen_copy_steps_for_return_data(exec_step, src_addr, dst_addr, length)?;
-
-    Ok(CopyEvent {
-        src_type: CopyDataType::Memory,
-        src_id: NumberOrHash::Number(state.call()?.last_callee_id),
-        src_addr,
-        src_addr_end,
-        dst_type: CopyDataType::Memory,
-        dst_id: NumberOrHash::Number(state.call()?.call_id),
-        dst_addr,
-        log_id: None,
-        rw_counter_start,
-        copy_bytes: CopyBytes::new(read_steps, Some(write_steps), Some(prev_bytes)),
-        access_list: vec![],
-    })
+    fn gen_copy_source(
+        state: &mut CircuitInputStateRef,
+        exec_step: &mut ExecStep,
+        stack_inputs: &[Word],
+        dst_addr: u64,
+        length: u64,
+    ) -> Result<CopySource, Error> {
+        let data_offset = stack_inputs[1].as_u64();
+
+        let last_callee_return_data_offset = state.call()?.last_callee_return_data_offset;
+        let last_callee_return_data_length = state.call()?.last_callee_return_data_length;
+        let (src_addr, src_addr_end) = (
+            last_callee_return_data_offset + data_offset,
+            last_callee_return_data_offset + last_callee_return_data_length,
+        );
+
+        let (read_steps, write_steps, prev_bytes) =
+            state.gen_copy_steps_for_return_data(exec_step, src_addr, dst_addr, length)?;
+
+        Ok(CopySource {
+            src_type: CopyDataType::Memory,
+            src_id: NumberOrHash::Number(state.call()?.last_callee_id),
+            src_addr,
+            src_addr_end,
+            copy_bytes: CopyBytes::new(read_steps, Some(write_steps), Some(prev_bytes)),
+        })
+    }
 }
 
 #[cfg(test)]
 mod return_tests {
-    use crate::mock::BlockData;
-    use eth_types::{bytecode, geth_types::GethData};
+    use crate::{
+        circuit_input_builder::{
+            CircuitInputBuilder, ExecState, TraceViolation, TraceViolationKind,
+        },
+        mock::BlockData,
+        operation::{Target, RW},
+    };
+    use eth_types::{bytecode, evm_types::OpcodeId, geth_types::GethData, ToBigEndian, Word};
     use mock::{
         test_ctx::{
             helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
@@ -119,8 +111,7 @@ mod return_tests {
         TestContext, MOCK_DEPLOYED_CONTRACT_BYTECODE,
     };
 
-    #[test]
-    fn test_ok() {
+    fn returndatacopy_block() -> GethData {
         let code = bytecode! {
             PUSH21(*MOCK_DEPLOYED_CONTRACT_BYTECODE)
             PUSH1(0)
@@ -148,6 +139,179 @@ mod return_tests {
             STOP
         };
         // Get the execution steps from the external tracer
+        TestContext::<2, 1>::new_with_logger_config(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+            LoggerConfig::default(),
+        )
+        .unwrap()
+        .into()
+    }
+
+    #[test]
+    fn test_ok() {
+        let block = returndatacopy_block();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_profile_opcode_timings() {
+        let block = returndatacopy_block();
+        let mut builder = BlockData::new_from_geth_data(block.clone())
+            .new_circuit_input_builder()
+            .with_profile(true);
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let timings = builder.opcode_timings();
+        assert!(!timings[&OpcodeId::RETURNDATACOPY].is_zero());
+    }
+
+    #[test]
+    fn test_rw_table_matches_golden() {
+        let block = returndatacopy_block();
+        let build = || {
+            let mut builder =
+                BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+            builder
+                .handle_block(&block.eth_block, &block.geth_traces)
+                .unwrap();
+            builder
+        };
+
+        let mut golden = build().block.container.rw_rows();
+        let builder = build();
+        assert_eq!(builder.diff_rw_table(&golden), None);
+
+        let removed = golden.remove(5);
+        let diff = builder.diff_rw_table(&golden).unwrap();
+        assert_eq!(diff.index, 5);
+        assert_eq!(diff.actual, Some(removed));
+        assert!(!diff.context.is_empty());
+    }
+
+    #[test]
+    fn test_copy_rw_continuity() {
+        let block = returndatacopy_block();
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        assert_eq!(builder.validate_copy_rw_continuity(), Ok(()));
+
+        // Replaying the last copy event claims RW counters a second time
+        let copy_events = &mut builder.block.copy_events;
+        let last = copy_events.len() - 1;
+        copy_events.push(copy_events[last].clone());
+        let overlaps = builder.validate_copy_rw_continuity().unwrap_err();
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].overlaps_with, Some(last));
+        assert!(overlaps[0].missing.is_empty());
+    }
+
+    #[test]
+    fn test_validate_trace() {
+        let block = returndatacopy_block();
+        let mut geth_trace = block.geth_traces[0].clone();
+        assert_eq!(CircuitInputBuilder::validate_trace(&geth_trace), Ok(()));
+
+        let gas = geth_trace.struct_logs[0].gas.0;
+        geth_trace.struct_logs[1].gas.0 = gas + 1;
+        let last = geth_trace.struct_logs.len() - 1;
+        geth_trace.struct_logs[last].depth = 3;
+
+        assert_eq!(
+            CircuitInputBuilder::validate_trace(&geth_trace),
+            Err(vec![
+                TraceViolation {
+                    step: 0,
+                    kind: TraceViolationKind::GasIncrease {
+                        gas,
+                        next_gas: gas + 1,
+                    },
+                },
+                TraceViolation {
+                    step: last - 1,
+                    kind: TraceViolationKind::DepthTransition {
+                        depth: 1,
+                        next_depth: 3,
+                    },
+                },
+            ])
+        );
+    }
+
+    #[cfg(feature = "enable-stack")]
+    #[test]
+    fn test_validate_trace_return_data_bounds() {
+        let block = returndatacopy_block();
+        let mut geth_trace = block.geth_traces[0].clone();
+        let index = geth_trace
+            .struct_logs
+            .iter()
+            .position(|step| step.op == OpcodeId::RETURNDATACOPY)
+            .unwrap();
+
+        // the callee returns 0x20 bytes, which are all copied
+        let stack = &mut geth_trace.struct_logs[index].stack.0;
+        let offset_index = stack.len() - 2;
+        assert_eq!(stack[offset_index], Word::zero());
+        stack[offset_index] = Word::one();
+
+        assert_eq!(
+            CircuitInputBuilder::validate_trace(&geth_trace),
+            Err(vec![TraceViolation {
+                step: index,
+                kind: TraceViolationKind::ReturnDataOutOfBounds {
+                    offset: Word::one(),
+                    length: 0x20.into(),
+                    return_data_len: 0x20,
+                },
+            }])
+        );
+    }
+
+    #[test]
+    fn test_copy_keeps_surrounding_memory() {
+        const PATTERN: u8 = 0xaa;
+        const RETURN_BYTE: u8 = 0x11;
+        const DST: usize = 0x08;
+        const LEN: usize = 0x10;
+
+        let code = bytecode! {
+            // memory[0x00..0x40] = PATTERN, memory[0x40..0x60] = RETURN_BYTE
+            PUSH32(Word::from_big_endian(&[PATTERN; 32]))
+            PUSH1(0x00)
+            MSTORE
+            PUSH32(Word::from_big_endian(&[PATTERN; 32]))
+            PUSH1(0x20)
+            MSTORE
+            PUSH32(Word::from_big_endian(&[RETURN_BYTE; 32]))
+            PUSH1(0x40)
+            MSTORE
+
+            // identity precompile echoes memory[0x40..0x60] as return data
+            PUSH1(0x00) // retSize
+            PUSH1(0x00) // retOffset
+            PUSH1(0x20) // argsSize
+            PUSH1(0x40) // argsOffset
+            PUSH1(0x04) // address
+            PUSH2(0xFFFF) // gas
+            STATICCALL
+            POP
+
+            PUSH1(LEN)
+            PUSH1(0x00)
+            PUSH1(DST)
+            RETURNDATACOPY
+            STOP
+        };
         let block: GethData = TestContext::<2, 1>::new_with_logger_config(
             None,
             account_0_code_account_1_no_code(code),
@@ -162,5 +326,95 @@ mod return_tests {
         builder
             .handle_block(&block.eth_block, &block.geth_traces)
             .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let call_id = tx.calls()[0].call_id;
+        let step = tx
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATACOPY))
+            .unwrap();
+        let writes = step
+            .bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::Memory)
+            .map(|op_ref| &builder.block.container.memory[op_ref.1])
+            .filter(|op| op.rw() == RW::WRITE && op.op().call_id() == call_id)
+            .collect::<Vec<_>>();
+        assert!(!writes.is_empty());
+
+        for op in writes {
+            let base = op.op().address().0;
+            let value = op.op().value().to_be_bytes();
+            let value_prev = op.op().value_prev().to_be_bytes();
+            for (idx, (byte, byte_prev)) in value.iter().zip(value_prev.iter()).enumerate() {
+                assert_eq!(*byte_prev, PATTERN);
+                if (DST..DST + LEN).contains(&(base + idx)) {
+                    assert_eq!(*byte, RETURN_BYTE, "copied byte at {}", base + idx);
+                } else {
+                    assert_eq!(byte, byte_prev, "neighbouring byte at {}", base + idx);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_spans() {
+        use std::sync::{Arc, Mutex};
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            Event, Metadata, Subscriber,
+        };
+
+        /// Records the name and `op` field of every created span.
+        #[derive(Clone, Default)]
+        struct SpanCollector(Arc<Mutex<Vec<(&'static str, String)>>>);
+
+        struct OpVisitor(String);
+
+        impl Visit for OpVisitor {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "op" {
+                    self.0 = format!("{value:?}");
+                }
+            }
+        }
+
+        impl Subscriber for SpanCollector {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut visitor = OpVisitor(String::new());
+                span.record(&mut visitor);
+                let mut spans = self.0.lock().unwrap();
+                spans.push((span.metadata().name(), visitor.0));
+                Id::from_u64(spans.len() as u64)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let block = returndatacopy_block();
+        let collector = SpanCollector::default();
+        tracing::subscriber::with_default(collector.clone(), || {
+            let mut builder =
+                BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+            builder
+                .handle_block(&block.eth_block, &block.geth_traces)
+                .unwrap();
+        });
+
+        let spans = collector.0.lock().unwrap();
+        assert!(spans.iter().any(|(name, _)| *name == "handle_block"));
+        assert!(spans.iter().any(|(name, _)| *name == "handle_tx"));
+        assert!(spans
+            .iter()
+            .any(|(name, op)| *name == "gen_associated_ops" && op == "RETURNDATACOPY"));
     }
 }
@@ -0,0 +1,504 @@
+use super::Opcode;
+use crate::{
+    circuit_input_builder::{CircuitInputStateRef, ExecStep},
+    operation::{AccountField, AccountOp, CallContextField, TxAccessListAccountOp},
+    Error,
+};
+use eth_types::{GethExecStep, ToAddress, ToWord, Word};
+
+/// Placeholder structure used to implement [`Opcode`] trait over it
+/// corresponding to the
+/// [`OpcodeId::SELFDESTRUCT`](crate::evm::OpcodeId::SELFDESTRUCT) `OpcodeId`.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Selfdestruct;
+
+impl Opcode for Selfdestruct {
+    fn gen_associated_ops(
+        state: &mut CircuitInputStateRef,
+        geth_steps: &[GethExecStep],
+    ) -> Result<Vec<ExecStep>, Error> {
+        let geth_step = &geth_steps[0];
+        let mut exec_step = state.new_step(geth_step)?;
+        let call = state.call()?.clone();
+        let sender = call.address;
+
+        for (field, value) in [
+            (CallContextField::TxId, state.tx_ctx.id().into()),
+            (CallContextField::IsSuccess, 1.into()),
+            (CallContextField::IsStatic, (call.is_static as u64).into()),
+            (
+                CallContextField::RwCounterEndOfReversion,
+                call.rw_counter_end_of_reversion.into(),
+            ),
+            (
+                CallContextField::IsPersistent,
+                (call.is_persistent as u64).into(),
+            ),
+            (CallContextField::CalleeAddress, sender.to_word()),
+        ] {
+            state.call_context_read(&mut exec_step, call.call_id, field, value)?;
+        }
+
+        // EIP-6780: only a contract created in this tx is destroyed.
+        let is_created = state.tx_created_account_read(&mut exec_step, sender)?;
+
+        let receiver = state.stack_pop(&mut exec_step)?.to_address();
+        #[cfg(feature = "enable-stack")]
+        assert_eq!(receiver, geth_step.stack.last()?.to_address());
+
+        // A non-existing receiver is created by the transfer below if the
+        // balance is non-zero.
+        let receiver_account = state.sdb.get_account(&receiver).1;
+        let receiver_exists = !receiver_account.is_empty();
+        let receiver_code_hash = if receiver_exists {
+            receiver_account.code_hash.to_word()
+        } else {
+            Word::zero()
+        };
+        state.account_read(
+            &mut exec_step,
+            receiver,
+            AccountField::CodeHash,
+            receiver_code_hash,
+        )?;
+
+        let is_warm = state.sdb.check_account_in_access_list(&receiver);
+        state.push_op_reversible(
+            &mut exec_step,
+            TxAccessListAccountOp {
+                tx_id: state.tx_ctx.id(),
+                address: receiver,
+                is_warm: true,
+                is_warm_prev: is_warm,
+            },
+        )?;
+
+        let (found, sender_account) = state.sdb.get_account(&sender);
+        if !found {
+            return Err(Error::AccountNotFound(sender));
+        }
+        let value = sender_account.balance;
+        log::trace!(
+            "self destruct, sender {:?} receiver {:?} value {:?}",
+            sender,
+            receiver,
+            value
+        );
+
+        let is_self = receiver == sender;
+
+        // The balance is moved out, except to the contract itself when it
+        // survives. A destroyed contract being its own receiver burns it.
+        // Since EIP-3529 there is no refund to account.
+        let balance = if is_self && !is_created {
+            value
+        } else {
+            Word::zero()
+        };
+        state.push_op_reversible(
+            &mut exec_step,
+            AccountOp {
+                address: sender,
+                field: AccountField::Balance,
+                value: balance,
+                value_prev: value,
+            },
+        )?;
+        if !is_self {
+            state.transfer_to(
+                &mut exec_step,
+                receiver,
+                receiver_exists,
+                false,
+                value,
+                true,
+            )?;
+        }
+
+        // The contract keeps its code, nonce and storage until the end of the
+        // tx, where the end tx step clears it.
+        if is_created && call.is_persistent {
+            state.sdb.destruct_account(sender);
+        }
+
+        if let Ok(caller) = state.caller_ctx_mut() {
+            caller.return_data.clear();
+        }
+        state.handle_return(
+            (None, None),
+            &mut [&mut exec_step],
+            geth_steps,
+            !call.is_root,
+        )?;
+        Ok(vec![exec_step])
+    }
+}
+
+#[cfg(test)]
+mod selfdestruct_tests {
+    use super::*;
+    use crate::{
+        circuit_input_builder::{CircuitInputBuilder, ExecState},
+        error::{ExecError, OogError},
+        mock::BlockData,
+        operation::{Target, RW},
+    };
+    use eth_types::{
+        bytecode,
+        evm_types::OpcodeId,
+        geth_types::GethData,
+        state_db::{Account, CodeDB},
+        Address,
+    };
+    use mock::{TestContext, MOCK_ACCOUNTS};
+    use pretty_assertions::assert_eq;
+
+    /// Returns the account writes of the SELFDESTRUCT step.
+    fn selfdestruct_account_ops(builder: &CircuitInputBuilder) -> Vec<AccountOp> {
+        step_account_ops(builder, ExecState::Op(OpcodeId::SELFDESTRUCT))
+    }
+
+    /// Returns the account writes of the first step in `exec_state`.
+    fn step_account_ops(builder: &CircuitInputBuilder, exec_state: ExecState) -> Vec<AccountOp> {
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == exec_state)
+            .unwrap();
+        step.bus_mapping_instance
+            .iter()
+            .filter(|op_ref| op_ref.0 == Target::Account)
+            .map(|op_ref| &builder.block.container.account[op_ref.as_usize()])
+            .filter(|op| op.rw() == RW::WRITE)
+            .map(|op| op.op().clone())
+            .collect()
+    }
+
+    fn field_write(ops: &[AccountOp], address: Address, field: AccountField) -> Option<&AccountOp> {
+        ops.iter()
+            .find(|op| op.address == address && op.field == field)
+    }
+
+    // SELFDESTRUCT is disabled on Scroll
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn selfdestruct_transfers_balance_to_new_account() {
+        let balance = Word::from(0x1234u64);
+        let code = bytecode! {
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            SELFDESTRUCT
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(balance)
+                    .code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let account_ops = selfdestruct_account_ops(&builder);
+        assert_eq!(
+            field_write(&account_ops, MOCK_ACCOUNTS[0], AccountField::Balance),
+            Some(&AccountOp {
+                address: MOCK_ACCOUNTS[0],
+                field: AccountField::Balance,
+                value: Word::zero(),
+                value_prev: balance,
+            })
+        );
+        assert_eq!(
+            field_write(&account_ops, MOCK_ACCOUNTS[2], AccountField::Balance),
+            Some(&AccountOp {
+                address: MOCK_ACCOUNTS[2],
+                field: AccountField::Balance,
+                value: balance,
+                value_prev: Word::zero(),
+            })
+        );
+        assert_eq!(builder.sdb.get_balance(&MOCK_ACCOUNTS[2]), balance);
+
+        // EIP-6780: a contract not created in this tx is not destroyed.
+        for field in [AccountField::Nonce, AccountField::CodeHash] {
+            assert_eq!(field_write(&account_ops, MOCK_ACCOUNTS[0], field), None);
+        }
+        assert!(!builder.sdb.get_account(&MOCK_ACCOUNTS[0]).1.is_empty());
+    }
+
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn selfdestruct_to_self_keeps_balance() {
+        let balance = Word::from(0x1234u64);
+        let code = bytecode! {
+            ADDRESS
+            SELFDESTRUCT
+        };
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(balance)
+                    .code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let account_ops = selfdestruct_account_ops(&builder);
+        assert_eq!(
+            account_ops,
+            vec![AccountOp {
+                address: MOCK_ACCOUNTS[0],
+                field: AccountField::Balance,
+                value: balance,
+                value_prev: balance,
+            }]
+        );
+        assert_eq!(builder.sdb.get_balance(&MOCK_ACCOUNTS[0]), balance);
+    }
+
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn selfdestruct_in_init_code_destroys_account() {
+        let value = Word::from(0x1234u64);
+        let code = bytecode! {
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            SELFDESTRUCT
+        };
+
+        let block: GethData = TestContext::<1, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[0].address).value(value).input(code.into());
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let contract = builder.block.txs()[0].calls()[0].address;
+        let account_ops = selfdestruct_account_ops(&builder);
+        assert_eq!(
+            field_write(&account_ops, contract, AccountField::Balance),
+            Some(&AccountOp {
+                address: contract,
+                field: AccountField::Balance,
+                value: Word::zero(),
+                value_prev: value,
+            })
+        );
+        for field in [AccountField::Nonce, AccountField::CodeHash] {
+            assert_eq!(field_write(&account_ops, contract, field), None);
+        }
+        assert_eq!(builder.sdb.get_balance(&MOCK_ACCOUNTS[2]), value);
+
+        // The contract is cleared at the end of the tx.
+        let account_ops = step_account_ops(&builder, ExecState::EndTx);
+        for field in [
+            AccountField::Balance,
+            AccountField::Nonce,
+            AccountField::CodeHash,
+        ] {
+            let op = field_write(&account_ops, contract, field).unwrap();
+            assert_eq!(op.value, Word::zero());
+        }
+        assert_eq!(builder.sdb.get_account(&contract).1, &Account::zero());
+    }
+
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn selfdestruct_contract_is_callable_until_end_of_tx() {
+        let child_code = bytecode! {
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            SELFDESTRUCT
+        };
+        let child_code_len = child_code.to_vec().len();
+        let init_code = bytecode! {
+            PUSH22(Word::from_big_endian(&child_code.to_vec()))
+            PUSH1(0)
+            MSTORE
+            PUSH1(child_code_len)
+            PUSH1(32 - child_code_len)
+            RETURN
+        };
+        let init_code_len = init_code.to_vec().len();
+        // The factory deploys the child, then calls it twice. The first call
+        // destroys it, the second one still runs its code.
+        let mut factory_code = bytecode! {
+            PUSH31(Word::from_big_endian(&init_code.to_vec()))
+            PUSH1(0)
+            MSTORE
+            PUSH1(init_code_len)
+            PUSH1(32 - init_code_len)
+            PUSH1(0)
+            CREATE
+        };
+        for _ in 0..2 {
+            factory_code.append(&bytecode! {
+                PUSH1(0)
+                PUSH1(0)
+                PUSH1(0)
+                PUSH1(0)
+                PUSH1(0)
+                DUP6
+                GAS
+                CALL
+                POP
+            });
+        }
+        factory_code.append(&bytecode! { STOP });
+
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(Word::from(0x1234u64))
+                    .code(factory_code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[1].address)
+                    .to(accs[0].address)
+                    .gas(Word::from(1_000_000));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let child = tx.calls()[1].address;
+        let selfdestruct_steps = tx
+            .steps()
+            .iter()
+            .filter(|step| step.exec_state == ExecState::Op(OpcodeId::SELFDESTRUCT))
+            .count();
+        assert_eq!(selfdestruct_steps, 2);
+
+        // Neither SELFDESTRUCT clears the child,
+        for field in [AccountField::Nonce, AccountField::CodeHash] {
+            assert_eq!(
+                field_write(&selfdestruct_account_ops(&builder), child, field),
+                None
+            );
+        }
+        // the end tx step does.
+        let account_ops = step_account_ops(&builder, ExecState::EndTx);
+        assert_eq!(
+            field_write(&account_ops, child, AccountField::Nonce),
+            Some(&AccountOp {
+                address: child,
+                field: AccountField::Nonce,
+                value: Word::zero(),
+                value_prev: Word::one(),
+            })
+        );
+        let op = field_write(&account_ops, child, AccountField::CodeHash).unwrap();
+        assert_eq!(op.value, Word::zero());
+        assert_eq!(op.value_prev, CodeDB::hash(&child_code.to_vec()).to_word());
+        assert_eq!(builder.sdb.get_account(&child).1, &Account::zero());
+    }
+
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn selfdestruct_out_of_gas() {
+        let code = bytecode! {
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            SELFDESTRUCT
+        };
+
+        // Enough for the constant gas of SELFDESTRUCT but not for the cold
+        // beneficiary account creation.
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(Word::from(0x1234u64))
+                    .code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[1].address)
+                    .to(accs[0].address)
+                    .gas(Word::from(21_003 + 6_000));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+        assert!(block.geth_traces[0].failed);
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::SELFDESTRUCT))
+            .unwrap();
+        assert_eq!(
+            step.error,
+            Some(ExecError::OutOfGas(OogError::SelfDestruct))
+        );
+        assert_eq!(step.bus_mapping_instance[0].0, Target::Stack);
+        assert!(step
+            .bus_mapping_instance
+            .iter()
+            .all(|op_ref| op_ref.0 != Target::Account));
+    }
+}
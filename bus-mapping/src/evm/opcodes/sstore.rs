@@ -64,6 +64,36 @@ pub fn calc_expected_tx_refund(
     tx_refund_new
 }
 
+/// Calculate the gas cost of a sstore op, base on EIP-2200 net gas metering
+/// and the EIP-2929 cold slot surcharge.
+pub fn calc_expected_gas_cost(
+    value: eth_types::Word,
+    value_prev: eth_types::Word,
+    original_value: eth_types::Word,
+    is_warm: bool,
+) -> u64 {
+    let warm_case_gas = if value_prev == value {
+        // no-op (2.1.1)
+        GasCost::WARM_ACCESS
+    } else if original_value == value_prev {
+        if original_value.is_zero() {
+            // create slot (2.1.2)
+            GasCost::SSTORE_SET
+        } else {
+            // write existing slot (2.1.2)
+            GasCost::SSTORE_RESET
+        }
+    } else {
+        // dirty update (2.2)
+        GasCost::WARM_ACCESS
+    };
+    if is_warm {
+        warm_case_gas.as_u64()
+    } else {
+        warm_case_gas.as_u64() + GasCost::COLD_SLOAD.as_u64()
+    }
+}
+
 /// Placeholder structure used to implement [`Opcode`] trait over it
 /// corresponding to the [`OpcodeId::SSTORE`](crate::evm::OpcodeId::SSTORE)
 /// `OpcodeId`.
@@ -165,6 +195,16 @@ impl Opcode for Sstore {
             },
         )?;
 
+        let gas_cost = calc_expected_gas_cost(value, value_prev, committed_value, is_warm);
+        if geth_step.gas_cost.as_u64() != gas_cost {
+            log::error!(
+                "sstore gas cost mismatch, trace {} expected {gas_cost}, value {value:?} \
+                 value_prev {value_prev:?} original {committed_value:?} is_warm {is_warm}",
+                geth_step.gas_cost.as_u64()
+            );
+            return Err(Error::InvalidGethExecTrace("sstore gas cost mismatch"));
+        }
+
         let refund = exec_step.gas_refund.0;
         let refund_expected =
             calc_expected_tx_refund(state.sdb.refund(), value, value_prev, committed_value);
@@ -215,7 +255,10 @@ mod sstore_tests {
         geth_types::GethData,
         Word,
     };
-    use mock::{test_ctx::helpers::tx_from_1_to_0, TestContext, MOCK_ACCOUNTS};
+    use mock::{
+        test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
+        TestContext, MOCK_ACCOUNTS,
+    };
     use pretty_assertions::assert_eq;
 
     fn test_ok(is_warm: bool) {
@@ -365,4 +408,123 @@ mod sstore_tests {
     fn sstore_opcode_impl_cold() {
         test_ok(false)
     }
+
+    fn test_gas_cost(original_value: u64, value: u64, expected_gas_cost: u64) {
+        let code = bytecode! {
+            PUSH32(value)
+            PUSH1(0x00u64)
+            SSTORE
+            STOP
+        };
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(MOCK_ACCOUNTS[0])
+                    .balance(Word::from(10u64.pow(19)))
+                    .code(code);
+                if original_value != 0 {
+                    accs[0].storage(vec![(0x00u64.into(), original_value.into())].into_iter());
+                }
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::SSTORE))
+            .unwrap();
+        assert_eq!(step.gas_cost.as_u64(), expected_gas_cost);
+        assert_eq!(
+            calc_expected_gas_cost(
+                value.into(),
+                original_value.into(),
+                original_value.into(),
+                false
+            ),
+            expected_gas_cost
+        );
+    }
+
+    #[test]
+    fn sstore_gas_cost_zero_to_zero() {
+        test_gas_cost(0, 0, 2200);
+    }
+
+    #[test]
+    fn sstore_gas_cost_zero_to_nonzero() {
+        test_gas_cost(0, 0x6f, 22100);
+    }
+
+    #[test]
+    fn sstore_gas_cost_nonzero_to_other() {
+        test_gas_cost(0x6f, 0x70, 5000);
+    }
+
+    #[test]
+    fn sstore_gas_cost_nonzero_to_zero() {
+        test_gas_cost(0x6f, 0, 5000);
+    }
+
+    #[test]
+    fn sstore_gas_cost_nonzero_to_same() {
+        test_gas_cost(0x6f, 0x6f, 2200);
+    }
+
+    #[test]
+    fn sstore_gas_cost_mismatch_is_rejected() {
+        let code = bytecode! {
+            PUSH1(0x6fu64)
+            PUSH1(0x00u64)
+            SSTORE
+            STOP
+        };
+        let mut block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let step = block.geth_traces[0]
+            .struct_logs
+            .iter_mut()
+            .find(|step| step.op == OpcodeId::SSTORE)
+            .unwrap();
+        step.gas_cost.0 += 1;
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        assert!(matches!(
+            builder.handle_block(&block.eth_block, &block.geth_traces),
+            Err(Error::InvalidGethExecTrace("sstore gas cost mismatch"))
+        ));
+    }
+
+    #[test]
+    fn sstore_gas_cost_dirty_slot() {
+        // original 0x6f, prev 0x70, new 0x71: dirty update on a warm slot
+        assert_eq!(
+            calc_expected_gas_cost(
+                Word::from(0x71u64),
+                Word::from(0x70u64),
+                Word::from(0x6fu64),
+                true
+            ),
+            GasCost::WARM_ACCESS.as_u64()
+        );
+    }
 }
@@ -137,4 +137,136 @@ mod tload_tests {
             )
         );
     }
+
+    #[test]
+    fn tload_sees_tstore_from_nested_call() {
+        let callee_code = bytecode! {
+            // Store 0x42 to transient storage slot 1 of the caller's account
+            PUSH1(0x42u64)
+            PUSH1(0x01u64)
+            TSTORE
+            STOP
+        };
+        let code = bytecode! {
+            PUSH1(0x00u64) // retSize
+            PUSH1(0x00u64) // retOffset
+            PUSH1(0x00u64) // argsSize
+            PUSH1(0x00u64) // argsOffset
+            PUSH20(MOCK_ACCOUNTS[2].to_word())
+            PUSH2(0xFFFFu64) // gas
+            DELEGATECALL
+            POP
+            // Load transient storage slot 1 after the callee returned
+            PUSH1(0x01u64)
+            TLOAD
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+                accs[2].address(MOCK_ACCOUNTS[2]).code(callee_code);
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::TLOAD))
+            .unwrap();
+
+        let transient_storage_op =
+            &builder.block.container.transient_storage[step.bus_mapping_instance[3].as_usize()];
+        assert_eq!(
+            (transient_storage_op.rw(), transient_storage_op.op()),
+            (
+                RW::READ,
+                &TransientStorageOp::new(
+                    MOCK_ACCOUNTS[0],
+                    Word::from(0x01u32),
+                    Word::from(0x42u32),
+                    Word::from(0x42u32),
+                    1,
+                )
+            )
+        );
+        let stack_op = &builder.block.container.stack[step.bus_mapping_instance[4].as_usize()];
+        assert_eq!(
+            (stack_op.rw(), stack_op.op().value()),
+            (RW::WRITE, &Word::from(0x42u32))
+        );
+    }
+
+    #[test]
+    fn transient_storage_cleared_between_txs() {
+        let code = bytecode! {
+            // Load slot 1, then set it so a later tx would see it if leaked
+            PUSH1(0x01u64)
+            TLOAD
+            POP
+            PUSH1(0x42u64)
+            PUSH1(0x01u64)
+            TSTORE
+            STOP
+        };
+
+        let block: GethData = TestContext::<3, 2>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(code);
+                accs[1]
+                    .address(MOCK_ACCOUNTS[1])
+                    .balance(Word::from(10u64.pow(19)));
+                accs[2]
+                    .address(MOCK_ACCOUNTS[2])
+                    .balance(Word::from(10u64.pow(19)));
+            },
+            |mut txs, accs| {
+                txs[0].from(accs[1].address).to(accs[0].address);
+                txs[1].from(accs[2].address).to(accs[0].address);
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        for tx in builder.block.txs() {
+            let step = tx
+                .steps()
+                .iter()
+                .find(|step| step.exec_state == ExecState::Op(OpcodeId::TLOAD))
+                .unwrap();
+            let stack_op = &builder.block.container.stack[step.bus_mapping_instance[4].as_usize()];
+            assert_eq!(
+                (stack_op.rw(), stack_op.op().value()),
+                (RW::WRITE, &Word::zero())
+            );
+        }
+        assert!(
+            !builder
+                .sdb
+                .get_transient_storage(&MOCK_ACCOUNTS[0], &Word::one())
+                .0
+        );
+    }
 }
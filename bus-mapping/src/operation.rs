@@ -98,6 +98,8 @@ pub enum Target {
     TxAccessListAccount,
     /// Means the target of the operation is the TxAccessListAccountStorage.
     TxAccessListAccountStorage,
+    /// Means the target of the operation is the TxCreatedAccount.
+    TxCreatedAccount,
     /// Means the target of the operation is the TxRefund.
     TxRefund,
     /// Means the target of the operation is the Account.
@@ -542,6 +544,55 @@ impl Op for TxAccessListAccountOp {
     }
 }
 
+/// Represents whether an account was created in the tx, set by a `BeginTx` or
+/// `CREATE*` step deploying it and read by `SELFDESTRUCT` (EIP-6780).
+#[derive(Clone, PartialEq, Eq)]
+pub struct TxCreatedAccountOp {
+    /// Transaction ID: Transaction index in the block starting at 1.
+    pub tx_id: usize,
+    /// Account Address
+    pub address: Address,
+    /// Whether the account was created in the tx.
+    pub is_created: bool,
+    /// Whether the account was created in the tx before the operation.
+    pub is_created_prev: bool,
+}
+
+impl fmt::Debug for TxCreatedAccountOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TxCreatedAccountOp { ")?;
+        f.write_fmt(format_args!(
+            "tx_id: {:?}, addr: {:?}, is_created_prev: {:?}, is_created: {:?}",
+            self.tx_id, self.address, self.is_created_prev, self.is_created
+        ))?;
+        f.write_str(" }")
+    }
+}
+
+impl PartialOrd for TxCreatedAccountOp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TxCreatedAccountOp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.tx_id, &self.address).cmp(&(&other.tx_id, &other.address))
+    }
+}
+
+impl Op for TxCreatedAccountOp {
+    fn into_enum(self) -> OpEnum {
+        OpEnum::TxCreatedAccount(self)
+    }
+
+    fn reverse(&self) -> Self {
+        let mut rev = self.clone();
+        swap(&mut rev.is_created, &mut rev.is_created_prev);
+        rev
+    }
+}
+
 /// Represents a change in the Storage AccessList implied by an `SSTORE` or
 /// `SLOAD` step of the [`ExecStep`](crate::circuit_input_builder::ExecStep).
 #[derive(Clone, PartialEq, Eq)]
@@ -1024,7 +1075,7 @@ impl Op for TxReceiptOp {
 
 /// Generic enum that wraps over all the operation types possible.
 /// In particular [`StackOp`], [`MemoryOp`] and [`StorageOp`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OpEnum {
     /// Stack
     Stack(StackOp),
@@ -1038,6 +1089,8 @@ pub enum OpEnum {
     TxAccessListAccount(TxAccessListAccountOp),
     /// TxAccessListAccountStorage
     TxAccessListAccountStorage(TxAccessListAccountStorageOp),
+    /// TxCreatedAccount
+    TxCreatedAccount(TxCreatedAccountOp),
     /// TxRefund
     TxRefund(TxRefundOp),
     /// Account
@@ -1052,6 +1105,31 @@ pub enum OpEnum {
     Start(StartOp),
 }
 
+/// A single row of the RW table: an operation of any kind together with its
+/// position in the global rw counter sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RwRow {
+    /// Global rw counter of the operation
+    pub rwc: RWCounter,
+    /// Read or write
+    pub rw: RW,
+    /// The operation itself
+    pub op: OpEnum,
+}
+
+/// First divergence found when comparing an RW table against a reference one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RwDiff {
+    /// Index of the first mismatching row
+    pub index: usize,
+    /// Row in the reference table, `None` if the reference table is shorter
+    pub expected: Option<RwRow>,
+    /// Row in the built table, `None` if the built table is shorter
+    pub actual: Option<RwRow>,
+    /// Rows of the built table surrounding the mismatch
+    pub context: Vec<RwRow>,
+}
+
 /// Operation is a Wrapper over a type that implements Op with a RWCounter.
 #[derive(Debug, Clone)]
 pub struct Operation<T: Op> {
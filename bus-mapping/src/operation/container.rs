@@ -1,7 +1,7 @@
 use super::{
-    AccountOp, CallContextOp, MemoryOp, Op, OpEnum, Operation, RWCounter, StackOp, StartOp,
+    AccountOp, CallContextOp, MemoryOp, Op, OpEnum, Operation, RWCounter, RwRow, StackOp, StartOp,
     StorageOp, Target, TransientStorageOp, TxAccessListAccountOp, TxAccessListAccountStorageOp,
-    TxLogOp, TxReceiptOp, TxRefundOp, RW,
+    TxCreatedAccountOp, TxLogOp, TxReceiptOp, TxRefundOp, RW,
 };
 use crate::exec_trace::OperationRef;
 use itertools::Itertools;
@@ -34,6 +34,8 @@ pub struct OperationContainer {
     pub tx_access_list_account: Vec<Operation<TxAccessListAccountOp>>,
     /// Operations of TxAccessListAccountStorageOp
     pub tx_access_list_account_storage: Vec<Operation<TxAccessListAccountStorageOp>>,
+    /// Operations of TxCreatedAccountOp
+    pub tx_created_account: Vec<Operation<TxCreatedAccountOp>>,
     /// Operations of TxRefundOp
     pub tx_refund: Vec<Operation<TxRefundOp>>,
     /// Operations of AccountOp
@@ -65,6 +67,7 @@ impl OperationContainer {
             transient_storage: Vec::new(),
             tx_access_list_account: Vec::new(),
             tx_access_list_account_storage: Vec::new(),
+            tx_created_account: Vec::new(),
             tx_refund: Vec::new(),
             account: Vec::new(),
             call_context: Vec::new(),
@@ -144,6 +147,14 @@ impl OperationContainer {
                     self.tx_access_list_account_storage.len() - 1,
                 ))
             }
+            OpEnum::TxCreatedAccount(op) => {
+                self.tx_created_account.push(if reversible {
+                    Operation::new_reversible(rwc, rw, op)
+                } else {
+                    Operation::new(rwc, rw, op)
+                });
+                OperationRef::from((Target::TxCreatedAccount, self.tx_created_account.len() - 1))
+            }
             OpEnum::TxRefund(op) => {
                 self.tx_refund.push(if reversible {
                     Operation::new_reversible(rwc, rw, op)
@@ -196,6 +207,33 @@ impl OperationContainer {
     pub fn sorted_storage(&self) -> Vec<Operation<StorageOp>> {
         self.storage.iter().sorted().cloned().collect()
     }
+
+    /// Returns all the operations contained inside of the container, except
+    /// the [`StartOp`] paddings, as [`RwRow`]s ordered by rw counter.
+    pub fn rw_rows(&self) -> Vec<RwRow> {
+        fn to_rows<T: Op>(ops: &[Operation<T>]) -> impl Iterator<Item = RwRow> + '_ {
+            ops.iter().map(|op| RwRow {
+                rwc: op.rwc(),
+                rw: op.rw(),
+                op: op.op().clone().into_enum(),
+            })
+        }
+
+        to_rows(&self.memory)
+            .chain(to_rows(&self.stack))
+            .chain(to_rows(&self.storage))
+            .chain(to_rows(&self.transient_storage))
+            .chain(to_rows(&self.tx_access_list_account))
+            .chain(to_rows(&self.tx_access_list_account_storage))
+            .chain(to_rows(&self.tx_created_account))
+            .chain(to_rows(&self.tx_refund))
+            .chain(to_rows(&self.account))
+            .chain(to_rows(&self.call_context))
+            .chain(to_rows(&self.tx_receipt))
+            .chain(to_rows(&self.tx_log))
+            .sorted_by_key(|row| row.rwc)
+            .collect()
+    }
 }
 
 #[cfg(test)]
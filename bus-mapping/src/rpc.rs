@@ -3,14 +3,15 @@
 
 use crate::Error;
 use eth_types::{
-    Address, Block, Bytes, EIP1186ProofResponse, GethExecTrace, GethPrestateTrace, Hash,
-    ResultGethExecTraces, ResultGethPrestateTraces, Transaction, Word, H256, U64,
+    geth_types::Account, Address, Block, Bytes, EIP1186ProofResponse, GethExecTrace,
+    GethPrestateTrace, Hash, ResultGethExecTraces, ResultGethPrestateTraces, ToBigEndian,
+    Transaction, Word, H256, U64,
 };
 pub use ethers_core::types::BlockNumber;
 use ethers_providers::JsonRpcClient;
 use serde::Serialize;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::util::GETH_TRACE_CHECK_LEVEL;
 
@@ -332,6 +333,58 @@ impl<P: JsonRpcClient> GethClient<P> {
         Ok(resp)
     }
 
+    /// Call `debug_traceCall` use prestateTracer to get the prestate of a
+    /// call executed on top of the state of a block, with the accounts in
+    /// `state_overrides` replacing the ones of that state.
+    pub async fn trace_call_prestate(
+        &self,
+        tx: &Transaction,
+        block_num: BlockNumber,
+        state_overrides: &BTreeMap<Address, Account>,
+    ) -> Result<HashMap<Address, GethPrestateTrace>, Error> {
+        // geth rejects calls setting both the legacy and the EIP-1559 gas
+        // price fields.
+        let call = json!({
+            "from": tx.from,
+            "to": tx.to,
+            "gas": tx.gas,
+            "gasPrice": tx.max_fee_per_gas.is_none().then_some(tx.gas_price).flatten(),
+            "maxFeePerGas": tx.max_fee_per_gas,
+            "maxPriorityFeePerGas": tx.max_priority_fee_per_gas,
+            "value": tx.value,
+            "data": tx.input,
+        });
+        // "state" replaces the whole storage of the account, unlike "stateDiff".
+        let state_overrides: BTreeMap<_, _> = state_overrides
+            .iter()
+            .map(|(address, account)| {
+                let storage: HashMap<_, _> = account
+                    .storage
+                    .iter()
+                    .map(|(key, value)| (H256(key.to_be_bytes()), H256(value.to_be_bytes())))
+                    .collect();
+                let account = json!({
+                    "nonce": U64::from(account.nonce.as_u64()),
+                    "balance": account.balance,
+                    "code": account.code,
+                    "state": storage,
+                });
+                (address, account)
+            })
+            .collect();
+        let num = serialize(&block_num);
+        let cfg = serialize(&serde_json::json! ({
+            "tracer": "prestateTracer",
+            "stateOverrides": state_overrides,
+        }));
+        let resp: HashMap<Address, GethPrestateTrace> = self
+            .0
+            .request("debug_traceCall", [call, num, cfg])
+            .await
+            .map_err(|e| Error::JSONRpcError(e.into()))?;
+        Ok(resp)
+    }
+
     /// Calls `eth_getCode` via JSON-RPC returning a contract code
     pub async fn get_code(
         &self,
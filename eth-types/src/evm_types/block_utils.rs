@@ -1,6 +1,6 @@
 //! Helper constants and utility functions for block
 
-use crate::{U256, U64};
+use crate::{Block, U256, U64};
 use ethers_core::utils::keccak256;
 
 /// Maximum range of previous blocks allowed inside BLOCKHASH opcode
@@ -27,3 +27,74 @@ pub fn is_valid_block_number(block_number: U256, current_block_number: U256) ->
                 .checked_sub(NUM_PREV_BLOCK_ALLOWED.into())
                 .unwrap_or_default()
 }
+
+/// Minimum blob base fee (EIP-4844)
+pub const MIN_BASE_FEE_PER_BLOB_GAS: u64 = 1;
+/// Controls the maximum rate of change of the blob base fee (EIP-4844)
+pub const BLOB_BASE_FEE_UPDATE_FRACTION: u64 = 3338477;
+
+/// Calculate the blob base fee of a block from its excess blob gas
+/// (EIP-4844), as returned by the BLOBBASEFEE opcode.
+pub fn calc_blob_base_fee(excess_blob_gas: u64) -> U256 {
+    fake_exponential(
+        MIN_BASE_FEE_PER_BLOB_GAS,
+        excess_blob_gas,
+        BLOB_BASE_FEE_UPDATE_FRACTION,
+    )
+}
+
+/// Blob base fee of `block`, from the excess blob gas of its header. Headers
+/// before Cancun have none, and get the minimum blob base fee.
+pub fn block_blob_base_fee<TX>(block: &Block<TX>) -> U256 {
+    let excess_blob_gas = block
+        .other
+        .get_deserialized::<U64>("excessBlobGas")
+        .and_then(Result::ok)
+        .unwrap_or_default();
+    calc_blob_base_fee(excess_blob_gas.as_u64())
+}
+
+/// Approximate `factor * e ** (numerator / denominator)` with its Taylor
+/// expansion, as specified by EIP-4844.
+fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> U256 {
+    let numerator = U256::from(numerator);
+    let denominator = U256::from(denominator);
+    let mut output = U256::zero();
+    let mut numerator_accum = U256::from(factor) * denominator;
+    let mut i = U256::one();
+    while !numerator_accum.is_zero() {
+        output += numerator_accum;
+        numerator_accum = numerator_accum * numerator / (denominator * i);
+        i += U256::one();
+    }
+    output / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_exponential_matches_eip4844() {
+        for (factor, numerator, denominator, expected) in [
+            (1, 0, 1, 1),
+            (38493, 0, 1000, 38493),
+            (0, 1234, 2345, 0),
+            (1, 2, 1, 6),
+            (1, 4, 2, 6),
+            (1, 3, 1, 16),
+            (1, 6, 2, 18),
+            (1, 4, 1, 49),
+            (1, 5, 1, 136),
+            (1, 5, 2, 11),
+            (2, 5, 2, 23),
+            (1, 50000000, 2225652, 5709098764),
+        ] {
+            assert_eq!(
+                fake_exponential(factor, numerator, denominator),
+                U256::from(expected)
+            );
+        }
+        assert_eq!(calc_blob_base_fee(0), U256::one());
+    }
+}
@@ -3,6 +3,7 @@
 #[cfg(feature = "scroll")]
 use crate::l2_types::BlockTrace;
 use crate::{
+    evm_types::block_utils::block_blob_base_fee,
     sign_types::{biguint_to_32bytes_le, ct_option_ok_or, recover_pk2, SignData, SECP256K1_Q},
     AccessList, Address, Block, Bytes, Error, GethExecTrace, Hash, ToBigEndian, ToLittleEndian,
     Word, U64,
@@ -212,6 +213,8 @@ pub struct BlockConstants {
     pub gas_limit: Word,
     /// base fee
     pub base_fee: Word,
+    /// blob base fee
+    pub blob_base_fee: Word,
 }
 
 impl<TX> TryFrom<&Block<TX>> for BlockConstants {
@@ -225,6 +228,7 @@ impl<TX> TryFrom<&Block<TX>> for BlockConstants {
             difficulty: block.difficulty,
             gas_limit: block.gas_limit,
             base_fee: block.base_fee_per_gas.ok_or(Error::IncompleteBlock)?,
+            blob_base_fee: block_blob_base_fee(block),
         })
     }
 }
@@ -238,6 +242,7 @@ impl BlockConstants {
         difficulty: Word,
         gas_limit: Word,
         base_fee: Word,
+        blob_base_fee: Word,
     ) -> BlockConstants {
         BlockConstants {
             coinbase,
@@ -246,6 +251,7 @@ impl BlockConstants {
             difficulty,
             gas_limit,
             base_fee,
+            blob_base_fee,
         }
     }
 }
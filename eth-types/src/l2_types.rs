@@ -10,7 +10,8 @@ use ethers_core::types::{
     Address, Bytes, U256, U64,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha3::{Digest, Keccak256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 #[cfg(feature = "enable-memory")]
 use crate::evm_types::Memory;
@@ -45,6 +46,31 @@ pub struct BlockTrace {
     pub start_l1_queue_index: u64,
 }
 
+impl BlockTrace {
+    /// Content hash of the trace which does not depend on the order in which
+    /// proofs are listed, so that semantically identical traces hash the same.
+    /// Execution results are derived from the rest of the trace and are not
+    /// part of the hash.
+    pub fn canonical_hash(&self) -> H256 {
+        let mut hasher = Keccak256::new();
+        let mut update = |bytes: &[u8]| {
+            hasher.update((bytes.len() as u64).to_be_bytes());
+            hasher.update(bytes);
+        };
+
+        update(&self.chain_id.to_be_bytes());
+        update(&self.start_l1_queue_index.to_be_bytes());
+        update(&serde_json::to_vec(&self.coinbase).expect("serialize coinbase"));
+        update(&serde_json::to_vec(&self.header).expect("serialize header"));
+        update(&serde_json::to_vec(&self.transactions).expect("serialize transactions"));
+        for storage_trace in std::iter::once(&self.storage_trace).chain(&self.tx_storage_trace) {
+            storage_trace.canonical_update(&mut update);
+        }
+
+        H256::from_slice(&hasher.finalize())
+    }
+}
+
 impl From<BlockTrace> for EthBlock {
     fn from(b: BlockTrace) -> Self {
         let mut txs = Vec::new();
@@ -268,6 +294,62 @@ pub struct StorageTrace {
     pub deletion_proofs: Vec<Bytes>,
 }
 
+impl StorageTrace {
+    /// Feed the trace into `update` with accounts and storage keys sorted and
+    /// the proof nodes of each entry sorted and deduplicated. `update` prefixes
+    /// every chunk with its length and every list is prefixed with its number
+    /// of entries, so that distinct traces never feed the same sequence.
+    fn canonical_update(&self, update: &mut impl FnMut(&[u8])) {
+        let sorted_nodes = |nodes: &[Bytes]| -> BTreeSet<Vec<u8>> {
+            nodes.iter().map(|node| node.to_vec()).collect()
+        };
+        let update_nodes = |update: &mut dyn FnMut(&[u8]), nodes: &BTreeSet<Vec<u8>>| {
+            update(&(nodes.len() as u64).to_be_bytes());
+            nodes.iter().for_each(|node| update(node));
+        };
+
+        update(self.root_before.as_bytes());
+        update(self.root_after.as_bytes());
+
+        // tell `None` apart from an empty map
+        update(&[self.proofs.is_some() as u8]);
+        let proofs = self
+            .proofs
+            .iter()
+            .flatten()
+            .map(|(address, nodes)| (address, sorted_nodes(nodes)))
+            .collect::<BTreeMap<_, _>>();
+        update(&(proofs.len() as u64).to_be_bytes());
+        for (address, nodes) in proofs {
+            update(address.as_bytes());
+            update_nodes(update, &nodes);
+        }
+
+        let storage_proofs = self
+            .storage_proofs
+            .iter()
+            .map(|(address, slots)| {
+                let slots = slots
+                    .iter()
+                    .map(|(key, nodes)| (key, sorted_nodes(nodes)))
+                    .collect::<BTreeMap<_, _>>();
+                (address, slots)
+            })
+            .collect::<BTreeMap<_, _>>();
+        update(&(storage_proofs.len() as u64).to_be_bytes());
+        for (address, slots) in storage_proofs {
+            update(address.as_bytes());
+            update(&(slots.len() as u64).to_be_bytes());
+            for (key, nodes) in slots {
+                update(&key.to_be_bytes());
+                update_nodes(update, &nodes);
+            }
+        }
+
+        update_nodes(update, &sorted_nodes(&self.deletion_proofs));
+    }
+}
+
 /// extension of `GethExecTrace`, with compatible serialize form
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ExecutionResult {
@@ -412,3 +494,92 @@ pub struct StorageProofWrapper {
     pub key: Option<U256>,
     pub value: Option<U256>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_trace() -> BlockTrace {
+        let account_a = Address::repeat_byte(0xaa);
+        let account_b = Address::repeat_byte(0xbb);
+        let node = |byte: u8| Bytes::from(vec![byte; 4]);
+
+        BlockTrace {
+            chain_id: 534352,
+            storage_trace: StorageTrace {
+                root_before: H256::repeat_byte(0x01),
+                root_after: H256::repeat_byte(0x02),
+                proofs: Some(HashMap::from([
+                    (account_a, vec![node(1), node(2)]),
+                    (account_b, vec![node(3)]),
+                ])),
+                storage_proofs: HashMap::from([(
+                    account_a,
+                    HashMap::from([
+                        (Word::from(1u64), vec![node(4), node(5)]),
+                        (Word::from(2u64), vec![node(6)]),
+                    ]),
+                )]),
+                deletion_proofs: vec![node(7), node(8)],
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn canonical_hash_ignores_proof_order() {
+        let trace = mock_trace();
+
+        let mut reordered = trace.clone();
+        let storage_trace = &mut reordered.storage_trace;
+        for nodes in storage_trace.proofs.iter_mut().flat_map(|p| p.values_mut()) {
+            nodes.reverse();
+        }
+        for nodes in storage_trace
+            .storage_proofs
+            .values_mut()
+            .flat_map(|slots| slots.values_mut())
+        {
+            nodes.reverse();
+        }
+        storage_trace.deletion_proofs.reverse();
+        assert_eq!(trace.canonical_hash(), reordered.canonical_hash());
+
+        let mut modified = trace.clone();
+        modified
+            .storage_trace
+            .deletion_proofs
+            .push(Bytes::from(vec![9; 4]));
+        assert_ne!(trace.canonical_hash(), modified.canonical_hash());
+    }
+
+    #[test]
+    fn canonical_hash_separates_proof_lists() {
+        let account_a = Address::repeat_byte(0xaa);
+        let account_b = Address::repeat_byte(0xbb);
+        let account_c = Address::repeat_byte(0xcc);
+        let with_proofs = |proofs| BlockTrace {
+            storage_trace: StorageTrace {
+                proofs,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // the node of A has the bytes of the address B
+        let a_owns_node = with_proofs(Some(HashMap::from([
+            (account_a, vec![Bytes::from(account_b.as_bytes().to_vec())]),
+            (account_c, vec![]),
+        ])));
+        let b_owns_node = with_proofs(Some(HashMap::from([
+            (account_a, vec![]),
+            (account_b, vec![Bytes::from(account_c.as_bytes().to_vec())]),
+        ])));
+        assert_ne!(a_owns_node.canonical_hash(), b_owns_node.canonical_hash());
+
+        assert_ne!(
+            with_proofs(None).canonical_hash(),
+            with_proofs(Some(HashMap::new())).canonical_hash()
+        );
+    }
+}
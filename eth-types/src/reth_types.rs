@@ -0,0 +1,228 @@
+//! Types used to deserialize the struct logger traces of reth.
+//!
+//! reth answers `debug_traceTransaction` with the alloy `DefaultFrame`, which
+//! mostly follows the geth layout but reports the execution errors with the
+//! names of the revm instruction results. The traces are merged with the
+//! `prestate` and `callTrace` of the same transaction, as done for geth, and
+//! converted into a [`GethExecTrace`] for the circuit input builder.
+
+use crate::{
+    evm_types::{Gas, GasCost, OpcodeId, ProgramCounter},
+    Error, GethCallTrace, GethExecError, GethExecStep, GethExecTrace, GethPrestateTrace,
+};
+use ethers_core::types::Address;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[cfg(feature = "enable-stack")]
+use crate::evm_types::Stack;
+#[cfg(feature = "enable-storage")]
+use crate::evm_types::Storage;
+#[cfg(feature = "enable-memory")]
+use crate::{evm_types::Memory, DebugU256, ToWord};
+
+/// Execution trace of a transaction returned by the reth struct logger.
+#[derive(Deserialize, Debug, Clone)]
+pub struct RethExecTrace {
+    /// Used gas
+    pub gas: u64,
+    /// True when the transaction has failed.
+    pub failed: bool,
+    /// Return value of execution, as 0x prefixed hex
+    #[serde(rename = "returnValue", default)]
+    pub return_value: String,
+    /// Exec steps
+    #[serde(rename = "structLogs")]
+    pub struct_logs: Vec<RethExecStep>,
+    /// prestate
+    pub prestate: HashMap<Address, GethPrestateTrace>,
+    /// callTrace
+    #[serde(rename = "callTrace")]
+    pub call_trace: GethCallTrace,
+}
+
+impl TryFrom<RethExecTrace> for GethExecTrace {
+    type Error = Error;
+
+    fn try_from(t: RethExecTrace) -> Result<Self, Self::Error> {
+        let struct_logs = t
+            .struct_logs
+            .into_iter()
+            .map(GethExecStep::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(GethExecTrace {
+            l1_fee: 0,
+            gas: Gas(t.gas),
+            failed: t.failed,
+            // geth reports the return value without the 0x prefix
+            return_value: t.return_value.trim_start_matches("0x").to_string(),
+            struct_logs,
+            account_after: vec![],
+            prestate: t.prestate,
+            call_trace: t.call_trace,
+        })
+    }
+}
+
+/// Execution step of a [`RethExecTrace`], the alloy `StructLog`.
+#[derive(Deserialize, Debug, Clone)]
+#[doc(hidden)]
+pub struct RethExecStep {
+    pub pc: u64,
+    pub op: OpcodeId,
+    pub gas: u64,
+    #[serde(rename = "gasCost")]
+    pub gas_cost: u64,
+    #[serde(default)]
+    pub refund: u64,
+    pub depth: u64,
+    pub error: Option<String>,
+    #[cfg(feature = "enable-stack")]
+    pub stack: Option<Vec<crate::Word>>,
+    #[cfg(feature = "enable-memory")]
+    pub memory: Option<Vec<DebugU256>>,
+    #[cfg(feature = "enable-storage")]
+    pub storage: Option<HashMap<crate::Word, crate::Word>>,
+}
+
+impl RethExecStep {
+    /// Convert the revm instruction result reported as error into the
+    /// corresponding geth error. Errors already in the geth format are kept.
+    fn geth_error(&self) -> Result<Option<GethExecError>, Error> {
+        let Some(error) = self.error.as_deref() else {
+            return Ok(None);
+        };
+
+        // revm does not report the stack size, only the stack of the step
+        // tells it.
+        #[cfg(feature = "enable-stack")]
+        let stack_len = self.stack.as_ref().map_or(0, |stack| stack.len()) as u64;
+        #[cfg(not(feature = "enable-stack"))]
+        let stack_len = 0;
+        let (min_stack_ptr, max_stack_ptr) = self.op.valid_stack_ptr_range();
+
+        let error = match error {
+            "OutOfGas" | "MemoryOOG" | "MemoryLimitOOG" | "PrecompileOOG" | "InvalidOperandOOG" => {
+                GethExecError::OutOfGas
+            }
+            "Revert" => GethExecError::ExecutionReverted,
+            "CallTooDeep" => GethExecError::Depth,
+            "OutOfFunds" => GethExecError::InsufficientBalance,
+            "CreateCollision" => GethExecError::ContractAddressCollision,
+            "CreateInitCodeSizeLimit" => GethExecError::MaxInitCodeSizeExceeded,
+            "CreateContractSizeLimit" => GethExecError::MaxCodeSizeExceeded,
+            "InvalidJump" => GethExecError::InvalidJump,
+            "StateChangeDuringStaticCall" | "CallNotAllowedInsideStatic" => {
+                GethExecError::WriteProtection
+            }
+            "OutOfOffset" => GethExecError::ReturnDataOutOfBounds,
+            "CreateContractStartingWithEF" => GethExecError::InvalidCode,
+            "NonceOverflow" => GethExecError::NonceUintOverflow,
+            "StackUnderflow" => GethExecError::StackUnderflow {
+                stack_len,
+                required: 1024 - max_stack_ptr as u64,
+            },
+            "StackOverflow" => GethExecError::StackOverflow {
+                stack_len,
+                limit: 1024 - min_stack_ptr as u64,
+            },
+            "OpcodeNotFound" | "InvalidFEOpcode" | "NotActivated" => {
+                GethExecError::InvalidOpcode(self.op)
+            }
+            _ => error.parse().map_err(|_| {
+                Error::TracingError(format!("unknown reth error {error} at pc {}", self.pc))
+            })?,
+        };
+        Ok(Some(error))
+    }
+}
+
+impl TryFrom<RethExecStep> for GethExecStep {
+    type Error = Error;
+
+    fn try_from(s: RethExecStep) -> Result<Self, Self::Error> {
+        Ok(GethExecStep {
+            error: s.geth_error()?,
+            pc: ProgramCounter(s.pc as usize),
+            op: s.op,
+            gas: Gas(s.gas),
+            gas_cost: GasCost(s.gas_cost),
+            refund: Gas(s.refund),
+            depth: s.depth as u16,
+            #[cfg(feature = "enable-stack")]
+            stack: s.stack.map_or_else(Stack::new, Stack::from),
+            #[cfg(feature = "enable-memory")]
+            memory: s.memory.map_or_else(Memory::default, |memory| {
+                Memory::from(memory.iter().map(|w| w.to_word()).collect::<Vec<_>>())
+            }),
+            #[cfg(feature = "enable-storage")]
+            storage: s.storage.map_or_else(Storage::empty, Storage::from),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reth_trace_into_geth_trace() {
+        let trace: RethExecTrace = serde_json::from_str(
+            r#"{
+                "failed": true,
+                "gas": 21012,
+                "returnValue": "0x",
+                "structLogs": [
+                    {
+                        "pc": 0,
+                        "op": "PUSH1",
+                        "gas": 79000,
+                        "gasCost": 3,
+                        "depth": 1,
+                        "stack": [],
+                        "memSize": 0
+                    },
+                    {
+                        "pc": 2,
+                        "op": "JUMP",
+                        "gas": 78997,
+                        "gasCost": 8,
+                        "depth": 1,
+                        "error": "InvalidJump",
+                        "stack": ["0x5"],
+                        "memSize": 0
+                    }
+                ],
+                "prestate": {},
+                "callTrace": {
+                    "calls": [],
+                    "error": null,
+                    "from": "0x0000000000000000000000000000000000000000",
+                    "to": null,
+                    "type": "CALL",
+                    "gas": "0x0",
+                    "gasUsed": "0x0",
+                    "value": "0x0"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let trace = GethExecTrace::try_from(trace).unwrap();
+        assert!(trace.failed);
+        assert_eq!(trace.return_value, "");
+        assert_eq!(trace.struct_logs.len(), 2);
+        assert_eq!(trace.struct_logs[0].error, None);
+        assert_eq!(trace.struct_logs[1].op, OpcodeId::JUMP);
+        assert_eq!(trace.struct_logs[1].error, Some(GethExecError::InvalidJump));
+    }
+
+    #[test]
+    fn reth_unknown_error() {
+        let step: RethExecStep = serde_json::from_str(
+            r#"{"pc": 7, "op": "STOP", "gas": 0, "gasCost": 0, "depth": 1, "error": "Unknown"}"#,
+        )
+        .unwrap();
+        assert!(GethExecStep::try_from(step).is_err());
+    }
+}
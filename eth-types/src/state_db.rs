@@ -6,7 +6,8 @@ use crate::{
     Address, Hash, Word, H256, KECCAK_CODE_HASH_EMPTY, U256,
 };
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    mem,
     sync::LazyLock,
 };
 
@@ -142,6 +143,8 @@ pub struct StateDB {
     // Accounts that have been through `SELFDESTRUCT` under the situation that `is_persistent` is
     // `true`. These accounts will be reset once `commit_tx` is called.
     destructed_account: HashSet<Address>,
+    // Accounts created in the current transaction, which `SELFDESTRUCT` destroys (EIP-6780).
+    created_account: HashSet<Address>,
     // Accounts that are still "empty", but an Account Rw {value_prev: 0x0, value: empty_code_hash}
     // has already been applied.
     // TODO: a better name?
@@ -240,6 +243,24 @@ impl StateDB {
         }
     }
 
+    /// Return the non-zero storage slots of the [`Account`] at `addr`, sorted
+    /// by key. Writes of the current tx in `dirty_storage` take precedence
+    /// over the committed storage.
+    pub fn account_storage_dump(&self, addr: &Address) -> Vec<(Word, Word)> {
+        let (_, acc) = self.get_account(addr);
+        let mut storage: BTreeMap<Word, Word> = acc.storage.iter().map(|(k, v)| (*k, *v)).collect();
+        storage.extend(
+            self.dirty_storage
+                .iter()
+                .filter(|((dirty_addr, _), _)| dirty_addr == addr)
+                .map(|((_, key), value)| (*key, *value)),
+        );
+        storage
+            .into_iter()
+            .filter(|(_, value)| !value.is_zero())
+            .collect()
+    }
+
     /// Get a mutable reference to the storage value from [`Account`] at `addr`,
     /// at `key`.  Returns false when the [`Account`] or `key` wasn't found in
     /// the state and it is created.  If the [`Account`] or `key` is not found
@@ -311,6 +332,22 @@ impl StateDB {
         debug_assert!(exist);
     }
 
+    /// Check whether `addr` was created in the current transaction.
+    pub fn check_account_created(&self, addr: &Address) -> bool {
+        self.created_account.contains(addr)
+    }
+
+    /// Mark `addr` as created in the current transaction.
+    pub fn add_created_account(&mut self, addr: Address) -> bool {
+        self.created_account.insert(addr)
+    }
+
+    /// Unmark `addr` as created in the current transaction.
+    pub fn remove_created_account(&mut self, addr: &Address) {
+        let exist = self.created_account.remove(addr);
+        debug_assert!(exist);
+    }
+
     /// Check whether `(addr, key)` exists in account storage access list.
     pub fn check_account_storage_in_access_list(&self, pair: &(Address, Word)) -> bool {
         self.access_list_account_storage.contains(pair)
@@ -328,12 +365,20 @@ impl StateDB {
         debug_assert!(exist);
     }
 
-    /// Set account as self destructed.
+    /// Set account as self destructed. The account is wiped once
+    /// `commit_tx` is called.
     pub fn destruct_account(&mut self, addr: Address) {
-        self.state.insert(addr, Account::zero());
         self.destructed_account.insert(addr);
     }
 
+    /// Return the accounts self destructed in the current tx, sorted by
+    /// address.
+    pub fn destructed_accounts(&self) -> Vec<Address> {
+        let mut accounts: Vec<Address> = self.destructed_account.iter().copied().collect();
+        accounts.sort();
+        accounts
+    }
+
     /// Retrieve refund.
     pub fn refund(&self) -> u64 {
         self.refund
@@ -356,7 +401,8 @@ impl StateDB {
         }
         self.dirty_storage = HashMap::new();
         self.touched_account = HashSet::new();
-        for addr in self.destructed_account.clone() {
+        self.created_account = HashSet::new();
+        for addr in mem::take(&mut self.destructed_account) {
             let (_, account) = self.get_account_mut(&addr);
             *account = ACCOUNT_ZERO.clone();
         }
@@ -428,4 +474,54 @@ mod statedb_tests {
         assert!(found);
         assert_eq!(value, &Word::from(102));
     }
+
+    #[test]
+    fn destruct_account_on_commit_tx() {
+        let addr = address!("0x0000000000000000000000000000000000000001");
+        let mut statedb = StateDB::new();
+        statedb.get_account_mut(&addr).1.nonce = Word::one();
+
+        // The account is kept until the end of the tx
+        statedb.destruct_account(addr);
+        assert_eq!(statedb.get_nonce(&addr), 1);
+        assert_eq!(statedb.destructed_accounts(), vec![addr]);
+        statedb.commit_tx();
+        assert_eq!(statedb.get_account(&addr).1, &Account::zero());
+        assert!(statedb.destructed_accounts().is_empty());
+
+        // and is not wiped again in later txs
+        statedb.get_account_mut(&addr).1.nonce = Word::one();
+        statedb.commit_tx();
+        assert_eq!(statedb.get_nonce(&addr), 1);
+    }
+
+    #[test]
+    fn account_storage_dump() {
+        let addr_a = address!("0x0000000000000000000000000000000000000001");
+        let addr_b = address!("0x0000000000000000000000000000000000000002");
+        let mut statedb = StateDB::new();
+
+        // Committed slots, including a zero one
+        for (key, value) in [(5u64, 50u64), (1, 10), (3, 0)] {
+            *statedb.get_storage_mut(&addr_a, &Word::from(key)).1 = Word::from(value);
+        }
+        *statedb.get_storage_mut(&addr_b, &Word::from(2)).1 = Word::from(20);
+
+        // Dirty writes overwrite, clear and add slots
+        statedb.set_storage(&addr_a, &Word::from(5), &Word::from(51));
+        statedb.set_storage(&addr_a, &Word::from(1), &Word::zero());
+        statedb.set_storage(&addr_a, &Word::from(4), &Word::from(40));
+
+        assert_eq!(
+            statedb.account_storage_dump(&addr_a),
+            vec![
+                (Word::from(4), Word::from(40)),
+                (Word::from(5), Word::from(51)),
+            ]
+        );
+        assert_eq!(
+            statedb.account_storage_dump(&addr_b),
+            vec![(Word::from(2), Word::from(20))]
+        );
+    }
 }
@@ -94,12 +94,13 @@ func FormatLogs(logs []logger.StructLog) []StructLogRes {
 }
 
 type Block struct {
-	Coinbase   common.Address `json:"coinbase"`
-	Timestamp  *hexutil.Big   `json:"timestamp"`
-	Number     *hexutil.Big   `json:"number"`
-	Difficulty *hexutil.Big   `json:"difficulty"`
-	GasLimit   *hexutil.Big   `json:"gas_limit"`
-	BaseFee    *hexutil.Big   `json:"base_fee"`
+	Coinbase    common.Address `json:"coinbase"`
+	Timestamp   *hexutil.Big   `json:"timestamp"`
+	Number      *hexutil.Big   `json:"number"`
+	Difficulty  *hexutil.Big   `json:"difficulty"`
+	GasLimit    *hexutil.Big   `json:"gas_limit"`
+	BaseFee     *hexutil.Big   `json:"base_fee"`
+	BlobBaseFee *hexutil.Big   `json:"blob_base_fee"`
 }
 
 type Account struct {
@@ -236,6 +237,7 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 		Difficulty:  toBigInt(config.Block.Difficulty),
 		Random:      &randao,
 		BaseFee:     toBigInt(config.Block.BaseFee),
+		BlobBaseFee: toBigInt(config.Block.BlobBaseFee),
 		GasLimit:    blockGasLimit,
 	}
 
@@ -98,6 +98,7 @@ const CIRCUITS_PARAMS: CircuitsParams = CircuitsParams {
         ec_mul: MAX_EC_MUL,
         ec_pairing: MAX_EC_PAIRING,
     },
+    chain_spec: None,
 };
 
 const EVM_CIRCUIT_DEGREE: u32 = 18;
@@ -45,6 +45,7 @@ const CIRCUITS_PARAMS: CircuitsParams = CircuitsParams {
         ec_mul: 10,
         ec_pairing: 4,
     },
+    chain_spec: None,
 };
 
 #[tokio::test]
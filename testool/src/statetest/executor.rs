@@ -2,8 +2,8 @@ use super::{AccountMatch, StateTest, StateTestResult};
 use crate::{config::TestSuite, utils::ETH_CHAIN_ID};
 use bus_mapping::circuit_input_builder::{CircuitInputBuilder, CircuitsParams, PrecompileEcParams};
 use eth_types::{
-    geth_types, state_db::CodeDB, Address, Bytes, GethExecTrace, ToBigEndian, ToWord, H256, U256,
-    U64,
+    evm_types::block_utils::calc_blob_base_fee, geth_types, state_db::CodeDB, Address, Bytes,
+    GethExecTrace, ToBigEndian, ToWord, H256, U256, U64,
 };
 use ethers_core::utils::keccak256;
 use ethers_signers::LocalWallet;
@@ -184,6 +184,7 @@ fn into_traceconfig(st: StateTest) -> (String, TraceConfig, StateTestResult) {
                 difficulty: st.env.current_difficulty,
                 gas_limit: U256::from(st.env.current_gas_limit),
                 base_fee: st.env.current_base_fee,
+                blob_base_fee: calc_blob_base_fee(0),
             },
 
             transactions: vec![geth_types::Transaction {
@@ -463,6 +464,7 @@ fn get_params_for_super_circuit_test() -> CircuitsParams {
             ec_mul: 50,
             ec_pairing: 2,
         },
+        chain_spec: None,
     }
 }
 */
@@ -487,6 +489,7 @@ fn get_params_for_sub_circuit_test() -> CircuitsParams {
             ec_mul: 50,
             ec_pairing: 2,
         },
+        chain_spec: None,
     }
 }
 
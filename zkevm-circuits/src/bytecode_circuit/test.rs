@@ -1,13 +1,15 @@
 #![allow(unused_imports)]
 use crate::{
     bytecode_circuit::{bytecode_unroller::*, circuit::BytecodeCircuit},
+    evm_circuit::witness::block_convert,
     table::BytecodeFieldTag,
     util::{is_push_with_data, keccak, unusable_rows, Challenges, Field, SubCircuit},
 };
-use bus_mapping::evm::OpcodeId;
-use eth_types::{state_db::CodeDB, Bytecode, ToWord, Word};
+use bus_mapping::{evm::OpcodeId, mock::BlockData};
+use eth_types::{bytecode, geth_types::GethData, state_db::CodeDB, Bytecode, ToWord, Word};
 use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
 use log::error;
+use mock::{eth, test_ctx::helpers::account_0_code_account_1_no_code, TestContext};
 
 #[test]
 fn bytecode_circuit_unusable_rows() {
@@ -320,3 +322,46 @@ fn bytecode_soundness_bug_1() {
     let prover = MockProver::<Fr>::run(k, &circuit, Vec::new()).unwrap();
     prover.assert_satisfied_par();
 }
+
+#[test]
+fn bytecode_shared_across_txs() {
+    let k = 9;
+    let code = bytecode! {
+        PUSH1(0x20)
+        PUSH1(0)
+        MSTORE
+        STOP
+    };
+    let block: GethData = TestContext::<2, 3>::new(
+        None,
+        account_0_code_account_1_no_code(code.clone()),
+        |mut txs, accs| {
+            for tx in txs.iter_mut() {
+                tx.to(accs[0].address).from(accs[1].address).value(eth(1));
+            }
+        },
+        |block, _tx| block.number(0xcafeu64),
+    )
+    .unwrap()
+    .into();
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+    let block = block_convert(&builder.block, &builder.code_db).unwrap();
+
+    // The bytecodes are keyed by code hash, so the contract called by every
+    // tx only takes rows once.
+    assert_eq!(
+        block
+            .bytecodes
+            .values()
+            .filter(|bytecode| bytecode.bytes == code.code())
+            .count(),
+        1
+    );
+
+    let circuit = BytecodeCircuit::<Fr>::new_from_block_sized(&block, 2usize.pow(k));
+    let prover = MockProver::<Fr>::run(k, &circuit, Vec::new()).unwrap();
+    prover.assert_satisfied_par();
+}
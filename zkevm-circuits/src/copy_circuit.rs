@@ -62,9 +62,9 @@ const NEXT_ROW: Rotation = Rotation(1);
 const NEXT_STEP: Rotation = Rotation(2);
 
 // Rows to enable but not use, that can be queried safely by the last event.
-const UNUSED_ROWS: usize = 2;
+pub(crate) const UNUSED_ROWS: usize = 2;
 // Rows to disable, so they do not query into Halo2 reserved rows.
-const DISABLED_ROWS: usize = 2;
+pub(crate) const DISABLED_ROWS: usize = 2;
 
 /// The rw table shared between evm circuit and state circuit
 #[derive(Clone, Debug)]
@@ -551,6 +551,7 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
 #[cfg(test)]
 mod evm_circuit_stats {
     use crate::{
+        copy_circuit::CopyCircuit,
         evm_circuit::{
             param::{
                 LOOKUP_CONFIG, N_BYTE_LOOKUPS, N_COPY_COLUMNS, N_PHASE1_COLUMNS, N_PHASE2_COLUMNS,
@@ -642,6 +643,34 @@ mod evm_circuit_stats {
         .run();
     }
 
+    #[test]
+    fn evm_circuit_auto_sized() {
+        let code = bytecode! {
+            PUSH1(0x20)
+            PUSH1(0x00)
+            PUSH1(0x00)
+            CALLDATACOPY
+            STOP
+        };
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(code).unwrap(),
+        )
+        .params(CircuitsParams {
+            max_evm_rows: 0,
+            max_copy_rows: 0,
+            ..Default::default()
+        })
+        .block_modifier(Box::new(|block| {
+            let evm_rows = block.circuits_params.max_evm_rows;
+            assert!(evm_rows.is_power_of_two());
+            assert!(evm_rows >= EvmCircuit::<Fr>::get_num_rows_required_no_padding(block));
+            let copy_rows = block.circuits_params.max_copy_rows;
+            assert!(copy_rows.is_power_of_two());
+            assert!(copy_rows >= CopyCircuit::<Fr>::min_num_rows_block(block).0);
+        }))
+        .run();
+    }
+
     /// Prints the stats of EVM circuit per execution state.  See
     /// `print_circuit_stats_by_states` for more details.
     ///
@@ -61,6 +61,7 @@ mod address;
 mod balance;
 mod begin_tx;
 mod bitwise;
+mod blobhash;
 mod block_ctx;
 mod blockhash;
 mod byte;
@@ -131,6 +132,8 @@ mod returndatasize;
 mod sar;
 mod sdiv_smod;
 mod selfbalance;
+#[cfg(not(feature = "scroll"))]
+mod selfdestruct;
 mod sha3;
 mod shl_shr;
 mod signed_comparator;
@@ -148,9 +151,10 @@ use address::AddressGadget;
 use balance::BalanceGadget;
 use begin_tx::BeginTxGadget;
 use bitwise::BitwiseGadget;
+use blobhash::BlobHashGadget;
 #[cfg(feature = "scroll")]
 use block_ctx::DifficultyGadget;
-use block_ctx::{BlockCtxU160Gadget, BlockCtxU256Gadget, BlockCtxU64Gadget};
+use block_ctx::{BlobBaseFeeGadget, BlockCtxU160Gadget, BlockCtxU256Gadget, BlockCtxU64Gadget};
 use blockhash::BlockHashGadget;
 use byte::ByteGadget;
 use calldatacopy::CallDataCopyGadget;
@@ -222,6 +226,8 @@ use returndatasize::ReturnDataSizeGadget;
 use sar::SarGadget;
 use sdiv_smod::SignedDivModGadget;
 use selfbalance::SelfbalanceGadget;
+#[cfg(not(feature = "scroll"))]
+use selfdestruct::SelfdestructGadget;
 use sha3::Sha3Gadget;
 use shl_shr::ShlShrGadget;
 use signed_comparator::SignedComparatorGadget;
@@ -329,7 +335,7 @@ pub(crate) struct ExecutionConfig<F> {
     create_gadget: Box<CreateGadget<F, false, { ExecutionState::CREATE }>>,
     create2_gadget: Box<CreateGadget<F, true, { ExecutionState::CREATE2 }>>,
     #[cfg(not(feature = "scroll"))]
-    selfdestruct_gadget: Box<DummyGadget<F, 1, 0, { ExecutionState::SELFDESTRUCT }>>,
+    selfdestruct_gadget: Box<SelfdestructGadget<F>>,
     signed_comparator_gadget: Box<SignedComparatorGadget<F>>,
     signextend_gadget: Box<SignextendGadget<F>>,
     sload_gadget: Box<SloadGadget<F>>,
@@ -342,6 +348,8 @@ pub(crate) struct ExecutionConfig<F> {
     block_ctx_u64_gadget: Box<BlockCtxU64Gadget<F>>,
     block_ctx_u160_gadget: Box<BlockCtxU160Gadget<F>>,
     block_ctx_u256_gadget: Box<BlockCtxU256Gadget<F>>,
+    blob_base_fee_gadget: Box<BlobBaseFeeGadget<F>>,
+    blobhash_gadget: Box<BlobHashGadget<F>>,
     #[cfg(feature = "scroll")]
     difficulty_gadget: Box<DifficultyGadget<F>>,
     // error gadgets
@@ -638,6 +646,8 @@ impl<F: Field> ExecutionConfig<F> {
             block_ctx_u64_gadget: configure_gadget!(),
             block_ctx_u160_gadget: configure_gadget!(),
             block_ctx_u256_gadget: configure_gadget!(),
+            blob_base_fee_gadget: configure_gadget!(),
+            blobhash_gadget: configure_gadget!(),
             #[cfg(feature = "scroll")]
             difficulty_gadget: configure_gadget!(),
             // error gadgets
@@ -1621,18 +1631,20 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::BLOCKCTXU64 => assign_exec_step!(self.block_ctx_u64_gadget),
             ExecutionState::BLOCKCTXU160 => assign_exec_step!(self.block_ctx_u160_gadget),
             ExecutionState::BLOCKCTXU256 => assign_exec_step!(self.block_ctx_u256_gadget),
+            ExecutionState::BLOBBASEFEE => assign_exec_step!(self.blob_base_fee_gadget),
+            ExecutionState::BLOBHASH => assign_exec_step!(self.blobhash_gadget),
             #[cfg(feature = "scroll")]
             ExecutionState::DIFFICULTY => assign_exec_step!(self.difficulty_gadget),
             ExecutionState::BLOCKHASH => assign_exec_step!(self.blockhash_gadget),
             ExecutionState::SELFBALANCE => assign_exec_step!(self.selfbalance_gadget),
             ExecutionState::CREATE => assign_exec_step!(self.create_gadget),
             ExecutionState::CREATE2 => assign_exec_step!(self.create2_gadget),
-            // dummy gadgets
-            ExecutionState::EXTCODECOPY => assign_exec_step!(self.extcodecopy_gadget),
             ExecutionState::SELFDESTRUCT => {
                 #[cfg(not(feature = "scroll"))]
                 assign_exec_step!(self.selfdestruct_gadget)
             }
+            // dummy gadgets
+            ExecutionState::EXTCODECOPY => assign_exec_step!(self.extcodecopy_gadget),
             // end of dummy gadgets
             ExecutionState::SHA3 => assign_exec_step!(self.sha3_gadget),
             ExecutionState::SHL_SHR => assign_exec_step!(self.shl_shr_gadget),
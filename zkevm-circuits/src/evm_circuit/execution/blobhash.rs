@@ -0,0 +1,141 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::N_BYTES_U64,
+        step::ExecutionState,
+        util::{
+            common_gadget::SameContextGadget,
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition,
+                Transition::Delta,
+            },
+            from_bytes,
+            math_gadget::IsZeroGadget,
+            not, sum, CachedRegion, Cell, Word,
+        },
+        witness::{Block, Call, ExecStep, Transaction},
+    },
+    table::{CallContextFieldTag, TxContextFieldTag},
+    util::{Expr, Field},
+};
+use bus_mapping::evm::OpcodeId;
+use eth_types::ToLittleEndian;
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+#[derive(Clone, Debug)]
+pub(crate) struct BlobHashGadget<F> {
+    same_context: SameContextGadget<F>,
+    index: Word<F>,
+    tx_id: Cell<F>,
+    blob_hash: Cell<F>,
+    is_blob_hash_zero: IsZeroGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for BlobHashGadget<F> {
+    const NAME: &'static str = "BLOBHASH";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::BLOBHASH;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let index = cb.query_word_rlc();
+        cb.stack_pop(index.expr());
+
+        let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
+
+        let blob_hash = cb.query_cell_phase2();
+        let is_blob_hash_zero = IsZeroGadget::construct(cb, blob_hash.expr());
+
+        // A non-zero hash must be the versioned hash at `index` in the tx
+        // table, which only holds indices that fit in a u64. A zero hash is
+        // what an out-of-range index reads; it is not checked against the
+        // number of blob hashes, since the tx circuit takes no blob txs.
+        cb.condition(not::expr(is_blob_hash_zero.expr()), |cb| {
+            cb.require_zero(
+                "index of a non-zero blob hash fits in u64",
+                sum::expr(&index.cells[N_BYTES_U64..]),
+            );
+            cb.tx_context_lookup(
+                tx_id.expr(),
+                TxContextFieldTag::BlobVersionedHash,
+                Some(from_bytes::expr(&index.cells[..N_BYTES_U64])),
+                blob_hash.expr(),
+            );
+        });
+
+        cb.stack_push(blob_hash.expr());
+
+        // State transition
+        let opcode = cb.query_cell();
+        let step_state_transition = StepStateTransition {
+            rw_counter: Delta(3.expr()),
+            program_counter: Delta(1.expr()),
+            stack_pointer: Delta(0.expr()),
+            gas_left: Delta(-OpcodeId::BLOBHASH.constant_gas_cost().expr()),
+            ..Default::default()
+        };
+        let same_context = SameContextGadget::construct(cb, opcode, step_state_transition);
+
+        Self {
+            same_context,
+            index,
+            tx_id,
+            blob_hash,
+            is_blob_hash_zero,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        tx: &Transaction,
+        _: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        self.same_context.assign_exec_step(region, offset, step)?;
+
+        let [index, blob_hash] = [0, 2].map(|idx| block.rws[step.rw_indices[idx]].stack_value());
+
+        self.index
+            .assign(region, offset, Some(index.to_le_bytes()))?;
+        self.tx_id
+            .assign(region, offset, Value::known(F::from(tx.id as u64)))?;
+        self.blob_hash
+            .assign(region, offset, region.word_rlc(blob_hash))?;
+        self.is_blob_hash_zero
+            .assign_value(region, offset, region.word_rlc(blob_hash))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "cancun"))]
+mod test {
+    use crate::test_util::CircuitTestBuilder;
+    use eth_types::{bytecode, Word};
+    use mock::TestContext;
+
+    fn test_ok(index: Word) {
+        let bytecode = bytecode! {
+            PUSH32(index)
+            BLOBHASH
+            STOP
+        };
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+        )
+        .run();
+    }
+
+    #[test]
+    fn blobhash_gadget_simple() {
+        test_ok(Word::zero());
+    }
+
+    #[test]
+    fn blobhash_gadget_out_of_range_index() {
+        test_ok(Word::MAX);
+    }
+}
@@ -188,6 +188,65 @@ impl<F: Field> ExecutionGadget<F> for BlockCtxU256Gadget<F> {
     }
 }
 
+#[derive(Clone, Debug)]
+pub(crate) struct BlobBaseFeeGadget<F> {
+    same_context: SameContextGadget<F>,
+    value: RandomLinearCombination<F, N_BYTES_WORD>,
+}
+
+impl<F: Field> ExecutionGadget<F> for BlobBaseFeeGadget<F> {
+    const NAME: &'static str = "BLOBBASEFEE";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::BLOBBASEFEE;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let value = cb.query_word_rlc();
+        cb.stack_push(value.expr());
+
+        // BLOBBASEFEE is not contiguous with COINBASE..BASEFEE, so the tag is
+        // fixed here instead of being derived from the opcode.
+        cb.block_lookup(
+            BlockContextFieldTag::BlobBaseFee.expr(),
+            cb.curr.state.block_number.expr(),
+            value.expr(),
+        );
+
+        // State transition
+        let opcode = cb.query_cell();
+        let step_state_transition = StepStateTransition {
+            rw_counter: Delta(1.expr()),
+            program_counter: Delta(1.expr()),
+            stack_pointer: Delta((-1).expr()),
+            gas_left: Delta(-OpcodeId::BLOBBASEFEE.constant_gas_cost().expr()),
+            ..Default::default()
+        };
+        let same_context = SameContextGadget::construct(cb, opcode, step_state_transition);
+
+        Self {
+            same_context,
+            value,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        _: &Transaction,
+        _: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        self.same_context.assign_exec_step(region, offset, step)?;
+
+        let value = block.rws[step.rw_indices[0]].stack_value();
+        self.value
+            .assign(region, offset, Some(value.to_le_bytes()))?;
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "scroll")]
 #[derive(Clone, Debug)]
 pub(crate) struct DifficultyGadget<F> {
@@ -274,4 +333,14 @@ mod test {
         };
         test_ok(bytecode);
     }
+
+    #[cfg(feature = "cancun")]
+    #[test]
+    fn blob_base_fee_gadget_test() {
+        let bytecode = bytecode! {
+            BLOBBASEFEE
+            STOP
+        };
+        test_ok(bytecode);
+    }
 }
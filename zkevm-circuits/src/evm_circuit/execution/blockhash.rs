@@ -251,4 +251,37 @@ mod test {
     fn blockhash_gadget_block_number_overflow() {
         test_ok(U256::MAX, 0xcafeu64);
     }
+
+    // The hash pushed on the stack must be the one of the block table.
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn blockhash_gadget_wrong_history_hash() {
+        let code = bytecode! {
+            PUSH1(3)
+            BLOCKHASH
+            STOP
+        };
+        let history_hashes = (0..5).map(|i| U256::from(0xbeefcafeu64 + i)).collect();
+        let ctx = TestContext::<2, 1>::new(
+            Some(history_hashes),
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block.number(5),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .block_modifier(Box::new(|block| {
+                // History hashes are those of blocks 0 to 4, only tamper with
+                // block 3 as the last one must be the parent hash.
+                let ctx = block.context.ctxs.get_mut(&5).unwrap();
+                ctx.history_hashes[3] += U256::one();
+            }))
+            .evm_checks(Some(Box::new(|prover, gate_rows, lookup_rows| {
+                assert!(prover
+                    .verify_at_rows_par(gate_rows.iter().cloned(), lookup_rows.iter().cloned())
+                    .is_err())
+            })))
+            .run();
+    }
 }
@@ -425,6 +425,14 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                     0.expr(),
                     Some(&mut callee_reversion_info),
                 );
+                // EIP 6780, a contract created in this tx can be destroyed in it
+                cb.tx_created_account_write(
+                    tx_id.expr(),
+                    contract_addr.clone(),
+                    1.expr(),
+                    0.expr(),
+                    Some(&mut callee_reversion_info),
+                );
 
                 cb.condition(init_code.has_length(), |cb| {
                     for (field_tag, value) in [
@@ -462,7 +470,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                         is_create: To(true.expr()),
                         code_hash: To(create.code_hash_word_rlc()),
                         gas_left: To(callee_gas_left),
-                        reversible_write_counter: To(1.expr() + transfer.reversible_w_delta()),
+                        reversible_write_counter: To(2.expr() + transfer.reversible_w_delta()),
                         ..StepStateTransition::new_context()
                     });
                 });
@@ -485,7 +493,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                         program_counter: Delta(1.expr()),
                         stack_pointer: Delta(2.expr() + IS_CREATE2.expr()),
                         gas_left: Delta(-gas_cost.expr()),
-                        reversible_write_counter: Delta(3.expr() + transfer.reversible_w_delta()),
+                        reversible_write_counter: Delta(4.expr() + transfer.reversible_w_delta()),
                         ..Default::default()
                     })
                 });
@@ -792,6 +800,7 @@ impl<F: Field, const IS_CREATE2: bool, const S: ExecutionState> ExecutionGadget<
                 F::one()
             } else {
                 rws.next(); // callee nonce += 1
+                rws.next(); // callee created in tx
                 rws.next(); // caller id
                 let rw = rws.next();
                 debug_assert_eq!(rw.tag(), RwTableTag::CallContext);
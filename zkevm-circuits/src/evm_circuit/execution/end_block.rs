@@ -207,10 +207,10 @@ impl<F: Field> ExecutionGadget<F> for EndBlockGadget<F> {
             .last_key_value()
             .map(|(_, b)| b.number)
             .unwrap_or_default();
-        let is_curie = bus_mapping::circuit_input_builder::curie::is_curie_fork(
-            block.chain_id,
-            last_block_number.as_u64(),
-        );
+        let is_curie = block
+            .circuits_params
+            .chain_spec(block.chain_id)
+            .is_curie_fork(last_block_number.as_u64());
         self.is_curie_fork_block
             .assign(region, offset, Value::known(F::from(is_curie as u64)))?;
 
@@ -57,6 +57,7 @@ pub(crate) struct EndTxGadget<F> {
     is_persistent: Cell<F>,
     tx_is_l1msg: IsEqualGadget<F>,
     tx_l1_fee: Cell<F>,
+    destructed_rws: Cell<F>,
 }
 
 impl<F: Field> ExecutionGadget<F> for EndTxGadget<F> {
@@ -232,10 +233,18 @@ impl<F: Field> ExecutionGadget<F> for EndTxGadget<F> {
         );
         // rwc_delta = 9 - is_first_tx + !tx_is_l1msg * (coinbase_transfer.rw_delta + 1)
 
+        // The accounts destroyed in this tx (EIP-6780) are cleared after the
+        // receipt. The state circuit checks those storage and account writes,
+        // only their number is witnessed here.
+        let destructed_rws = cb.query_cell();
+        // rwc_delta = 9 - is_first_tx + !tx_is_l1msg * (coinbase_transfer.rw_delta + 1)
+        //             + destructed_rws
+
         // The next state of `end_tx` can only be 'begin_tx' or 'end_inner_block'
 
         let rw_counter_offset = 9.expr() - is_first_tx.expr()
-            + not::expr(tx_is_l1msg.expr()) * (coinbase_transfer.rw_delta() + 1.expr());
+            + not::expr(tx_is_l1msg.expr()) * (coinbase_transfer.rw_delta() + 1.expr())
+            + destructed_rws.expr();
         cb.condition(
             cb.next.execution_state_selector([ExecutionState::BeginTx]),
             |cb| {
@@ -297,6 +306,7 @@ impl<F: Field> ExecutionGadget<F> for EndTxGadget<F> {
             is_persistent,
             tx_is_l1msg,
             tx_l1_fee,
+            destructed_rws,
         }
     }
 
@@ -473,6 +483,14 @@ impl<F: Field> ExecutionGadget<F> for EndTxGadget<F> {
             offset,
             Value::known(F::from(call.is_persistent as u64)),
         )?;
+        let destructed_rws = step
+            .rw_indices
+            .iter()
+            .rev()
+            .take_while(|(tag, _)| matches!(tag, RwTableTag::Account | RwTableTag::AccountStorage))
+            .count();
+        self.destructed_rws
+            .assign(region, offset, Value::known(F::from(destructed_rws as u64)))?;
 
         Ok(())
     }
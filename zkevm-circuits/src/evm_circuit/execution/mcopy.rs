@@ -261,5 +261,22 @@ mod test {
         test_ok(Word::from("0x0"), Word::from("0x20"), 0x40);
     }
 
+    #[test]
+    fn mcopy_overlap_backward() {
+        // dest before src, the copy reads bytes it has already overwritten
+        test_ok(Word::from("0x30"), Word::from("0x10"), 0x40);
+        test_ok(Word::from("0x21"), Word::from("0x20"), 0x3f);
+    }
+
+    #[test]
+    fn mcopy_memory_expansion() {
+        // only the source range expands the memory
+        test_ok(Word::from("0x200"), Word::from("0x0"), 0x20);
+        // only the destination range expands the memory
+        test_ok(Word::from("0x0"), Word::from("0x200"), 0x20);
+        // both expand, the farther end is used
+        test_ok(Word::from("0x100"), Word::from("0x180"), 0x90);
+    }
+
     // mcopy OOG cases added in ./execution/error_oog_memory_copy.rs
 }
@@ -0,0 +1,582 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::{N_BYTES_ACCOUNT_ADDRESS, N_BYTES_GAS},
+        step::ExecutionState,
+        util::{
+            common_gadget::{RestoreContextGadget, TransferToGadget},
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, ReversionInfo, StepStateTransition,
+                Transition::{Delta, Same, To},
+            },
+            from_bytes,
+            math_gadget::{IsEqualGadget, IsZeroGadget, RangeCheckGadget},
+            not, CachedRegion, Cell, StepRws, Word,
+        },
+        witness::{Block, Call, ExecStep, Transaction},
+    },
+    table::{AccountFieldTag, CallContextFieldTag},
+    util::{Expr, Field},
+};
+use bus_mapping::evm::OpcodeId;
+use either::Either;
+use eth_types::{evm_types::GasCost, ToAddress, ToLittleEndian, ToScalar};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+/// Gadget for SELFDESTRUCT. The balance of the current account is moved to the
+/// receiver, created if it doesn't exist. Per EIP-6780 only an account created
+/// in this tx is destroyed, and it burns the balance when it is its own
+/// receiver. It keeps its code, nonce and storage until EndTx clears it. Since
+/// EIP-3529 there is no refund.
+#[derive(Clone, Debug)]
+pub(crate) struct SelfdestructGadget<F> {
+    opcode: Cell<F>,
+    tx_id: Cell<F>,
+    is_static: Cell<F>,
+    reversion_info: ReversionInfo<F>,
+    callee_address: Cell<F>,
+    is_created: Cell<F>,
+    receiver_word: Word<F>,
+    receiver_code_hash: Cell<F>,
+    receiver_not_exists: IsZeroGadget<F>,
+    is_warm: Cell<F>,
+    value: Word<F>,
+    value_is_zero: IsZeroGadget<F>,
+    is_self: IsEqualGadget<F>,
+    transfer: TransferToGadget<F>,
+    sufficient_gas_left: RangeCheckGadget<F, N_BYTES_GAS>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for SelfdestructGadget<F> {
+    const NAME: &'static str = "SELFDESTRUCT";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::SELFDESTRUCT;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let opcode = cb.query_cell();
+        cb.opcode_lookup(opcode.expr(), 1.expr());
+        // We do the responsible opcode check explicitly here because we're not using
+        // the `SameContextGadget` for `SELFDESTRUCT`.
+        cb.require_equal(
+            "Opcode should be SELFDESTRUCT",
+            opcode.expr(),
+            OpcodeId::SELFDESTRUCT.expr(),
+        );
+
+        let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
+        // Call ends with SELFDESTRUCT must be successful
+        cb.call_context_lookup(false.expr(), None, CallContextFieldTag::IsSuccess, 1.expr());
+        let is_static = cb.call_context(None, CallContextFieldTag::IsStatic);
+        cb.require_zero("is_static is false", is_static.expr());
+        let mut reversion_info = cb.reversion_info_read(None);
+        let callee_address = cb.call_context(None, CallContextFieldTag::CalleeAddress);
+        // Whether the current account was created in this tx, as recorded by
+        // the CREATE/CREATE2 or the create tx that deployed it.
+        let is_created = cb.query_bool();
+        cb.tx_created_account_read(tx_id.expr(), callee_address.expr(), is_created.expr());
+
+        let receiver_word = cb.query_word_rlc();
+        let receiver = from_bytes::expr(&receiver_word.cells[..N_BYTES_ACCOUNT_ADDRESS]);
+        cb.stack_pop(receiver_word.expr());
+
+        let receiver_code_hash = cb.query_cell_phase2();
+        // For non-existing accounts the code_hash must be 0 in the rw_table.
+        cb.account_read(
+            receiver.expr(),
+            AccountFieldTag::CodeHash,
+            receiver_code_hash.expr(),
+        );
+        let receiver_not_exists = IsZeroGadget::construct(cb, receiver_code_hash.expr());
+
+        let is_warm = cb.query_bool();
+        cb.account_access_list_write(
+            tx_id.expr(),
+            receiver.expr(),
+            1.expr(),
+            is_warm.expr(),
+            Some(&mut reversion_info),
+        );
+
+        // Move the balance out, the balance being the transferred value. It
+        // is kept only by a surviving account being its own receiver.
+        let value = cb.query_word_rlc();
+        let value_is_zero = IsZeroGadget::construct(cb, value.expr());
+        let is_self = IsEqualGadget::construct(cb, receiver.expr(), callee_address.expr());
+        cb.account_write(
+            callee_address.expr(),
+            AccountFieldTag::Balance,
+            is_self.expr() * not::expr(is_created.expr()) * value.expr(),
+            value.expr(),
+            Some(&mut reversion_info),
+        );
+
+        let transfer = cb.condition(not::expr(is_self.expr()), |cb| {
+            TransferToGadget::construct_with_is_zero(
+                cb,
+                receiver.expr(),
+                not::expr(receiver_not_exists.expr()),
+                0.expr(),
+                receiver_code_hash.expr(),
+                value.clone(),
+                Either::Right(value_is_zero.expr()),
+                Some(&mut reversion_info),
+            )
+        });
+        // +1 Write TxAccessListAccount, +1 Write Account (current) Balance
+        // +1 Write Account (receiver) CodeHash if it is created
+        // +1 Write Account (receiver) Balance if the value is non-zero
+        let reversible_write_counter_delta = 2.expr()
+            + not::expr(is_self.expr())
+                * not::expr(value_is_zero.expr())
+                * (receiver_not_exists.expr() + 1.expr());
+
+        let gas_cost = GasCost::SELFDESTRUCT.expr()
+            + not::expr(is_warm.expr()) * GasCost::COLD_ACCOUNT_ACCESS.expr()
+            + not::expr(value_is_zero.expr())
+                * receiver_not_exists.expr()
+                * GasCost::NEW_ACCOUNT.expr();
+        let sufficient_gas_left =
+            RangeCheckGadget::construct(cb, cb.curr.state.gas_left.expr() - gas_cost.expr());
+
+        let is_to_end_tx = cb.next.execution_state_selector([ExecutionState::EndTx]);
+        cb.require_equal(
+            "Go to EndTx only when is_root",
+            cb.curr.state.is_root.expr(),
+            is_to_end_tx,
+        );
+
+        // When it's a root call
+        cb.condition(cb.curr.state.is_root.expr(), |cb| {
+            cb.require_step_state_transition(StepStateTransition {
+                call_id: Same,
+                rw_counter: Delta(cb.rw_counter_offset()),
+                gas_left: Delta(-gas_cost.expr()),
+                reversible_write_counter: Delta(reversible_write_counter_delta.expr()),
+                end_tx: To(1.expr()),
+                ..StepStateTransition::any()
+            });
+        });
+
+        // When it's an internal call
+        let restore_context = cb.condition(1.expr() - cb.curr.state.is_root.expr(), |cb| {
+            RestoreContextGadget::construct2(
+                cb,
+                true.expr(),
+                gas_cost.expr(),
+                0.expr(),
+                0.expr(),
+                0.expr(),
+                0.expr(),
+                reversible_write_counter_delta.expr(),
+            )
+        });
+
+        Self {
+            opcode,
+            tx_id,
+            is_static,
+            reversion_info,
+            callee_address,
+            is_created,
+            receiver_word,
+            receiver_code_hash,
+            receiver_not_exists,
+            is_warm,
+            value,
+            value_is_zero,
+            is_self,
+            transfer,
+            sufficient_gas_left,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let opcode = step.opcode.unwrap();
+        self.opcode
+            .assign(region, offset, Value::known(F::from(opcode.as_u64())))?;
+
+        self.tx_id
+            .assign(region, offset, Value::known(F::from(tx.id as u64)))?;
+        self.is_static
+            .assign(region, offset, Value::known(F::from(call.is_static as u64)))?;
+        self.reversion_info.assign(
+            region,
+            offset,
+            call.rw_counter_end_of_reversion,
+            call.is_persistent,
+        )?;
+        let callee_address = call
+            .callee_address
+            .to_scalar()
+            .expect("unexpected Address -> Scalar conversion failure");
+        self.callee_address
+            .assign(region, offset, Value::known(callee_address))?;
+
+        let mut rws = StepRws::new(block, step);
+        rws.offset_add(6);
+
+        let (is_created, _) = rws.next().tx_created_account_value_pair();
+        self.is_created
+            .assign(region, offset, Value::known(F::from(is_created as u64)))?;
+
+        let receiver_word = rws.next().stack_value();
+        self.receiver_word
+            .assign(region, offset, Some(receiver_word.to_le_bytes()))?;
+        let receiver = receiver_word.to_address();
+
+        let receiver_code_hash = rws.next().account_codehash_pair().0;
+        self.receiver_code_hash
+            .assign(region, offset, region.code_hash(receiver_code_hash))?;
+        self.receiver_not_exists.assign_value(
+            region,
+            offset,
+            region.code_hash(receiver_code_hash),
+        )?;
+        let receiver_exists = !receiver_code_hash.is_zero();
+
+        let (_, is_warm) = rws.next().tx_access_list_value_pair();
+        self.is_warm
+            .assign(region, offset, Value::known(F::from(is_warm as u64)))?;
+
+        let (_, value) = rws.next().account_balance_pair();
+        self.value
+            .assign(region, offset, Some(value.to_le_bytes()))?;
+        self.value_is_zero
+            .assign_value(region, offset, region.word_rlc(value))?;
+        let is_self = receiver == call.callee_address;
+        self.is_self.assign(
+            region,
+            offset,
+            receiver
+                .to_scalar()
+                .expect("unexpected Address -> Scalar conversion failure"),
+            callee_address,
+        )?;
+
+        let mut transfer_rws = 0;
+        if !is_self {
+            self.transfer.assign_from_rws(
+                region,
+                offset,
+                receiver_exists,
+                false,
+                value,
+                &mut rws,
+            )?;
+            if !value.is_zero() {
+                transfer_rws = if receiver_exists { 1 } else { 3 };
+            }
+        }
+
+        self.sufficient_gas_left
+            .assign(region, offset, F::from(step.gas_left - step.gas_cost))?;
+
+        if !call.is_root {
+            self.restore_context
+                .assign(region, offset, block, call, step, 11 + transfer_rws)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{evm_circuit::step::ExecutionState, test_util::CircuitTestBuilder, witness::Rw};
+    use eth_types::{address, bytecode, Address, Bytecode, ToWord, Word};
+    use mock::{eth, TestContext, MOCK_ACCOUNTS};
+
+    fn selfdestruct_code(receiver: Address) -> Bytecode {
+        bytecode! {
+            PUSH20(receiver.to_word())
+            SELFDESTRUCT
+        }
+    }
+
+    fn test_root_ok(receiver: Address, balance: Word) {
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000123"))
+                    .balance(eth(10));
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(balance)
+                    .code(selfdestruct_code(receiver));
+                accs[2].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[0].address)
+                    .to(accs[1].address)
+                    .gas(Word::from(100_000));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    fn test_init_code_ok(init_code: Bytecode) {
+        let ctx = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000123"))
+                    .balance(eth(10));
+                accs[1].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[0].address)
+                    .value(eth(1))
+                    .gas(Word::from(100_000))
+                    .input(init_code.into());
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    fn test_internal_ok(receiver: Address, balance: Word, caller_reverts: bool) {
+        let mut caller_code = bytecode! {
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0)
+            PUSH1(0x20)
+            GAS
+            CALL
+        };
+        if caller_reverts {
+            caller_code.append(&bytecode! {
+                PUSH1(0)
+                PUSH1(0)
+                REVERT
+            });
+        } else {
+            caller_code.append(&bytecode! { STOP });
+        }
+
+        let ctx = TestContext::<4, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000123"))
+                    .balance(eth(10));
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(eth(1))
+                    .code(caller_code);
+                accs[2]
+                    .address(address!("0x0000000000000000000000000000000000000020"))
+                    .balance(balance)
+                    .code(selfdestruct_code(receiver));
+                accs[3].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[0].address)
+                    .to(accs[1].address)
+                    .gas(Word::from(200_000));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    #[test]
+    fn selfdestruct_gadget_root() {
+        // Existing receiver
+        test_root_ok(MOCK_ACCOUNTS[0], eth(1));
+        // Non-existing receiver, created by the transfer
+        test_root_ok(MOCK_ACCOUNTS[1], eth(1));
+        // Non-existing receiver, not created when there is nothing to move
+        test_root_ok(MOCK_ACCOUNTS[1], Word::zero());
+        // The balance is kept when the receiver is the current account
+        test_root_ok(
+            address!("0x0000000000000000000000000000000000000010"),
+            eth(1),
+        );
+    }
+
+    #[test]
+    fn selfdestruct_gadget_init_code() {
+        // The account created in this tx is destroyed
+        test_init_code_ok(selfdestruct_code(MOCK_ACCOUNTS[0]));
+        // and burns the balance when it is the receiver
+        test_init_code_ok(bytecode! {
+            ADDRESS
+            SELFDESTRUCT
+        });
+    }
+
+    #[test]
+    fn selfdestruct_gadget_callable_until_end_of_tx() {
+        let child_code = selfdestruct_code(MOCK_ACCOUNTS[0]);
+        let child_code_len = child_code.to_vec().len();
+        let init_code = bytecode! {
+            PUSH22(Word::from_big_endian(&child_code.to_vec()))
+            PUSH1(0)
+            MSTORE
+            PUSH1(child_code_len)
+            PUSH1(32 - child_code_len)
+            RETURN
+        };
+        let init_code_len = init_code.to_vec().len();
+        // Deploy the child and call it twice, the second call runs its code
+        // again as it is only cleared at the end of the tx.
+        let mut factory_code = bytecode! {
+            PUSH31(Word::from_big_endian(&init_code.to_vec()))
+            PUSH1(0)
+            MSTORE
+            PUSH1(init_code_len)
+            PUSH1(32 - init_code_len)
+            PUSH1(0)
+            CREATE
+        };
+        for _ in 0..2 {
+            factory_code.append(&bytecode! {
+                PUSH1(0)
+                PUSH1(0)
+                PUSH1(0)
+                PUSH1(0)
+                PUSH1(0)
+                DUP6
+                GAS
+                CALL
+                POP
+            });
+        }
+        factory_code.append(&bytecode! { STOP });
+
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000123"))
+                    .balance(eth(10));
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(eth(1))
+                    .code(factory_code);
+                accs[2].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[0].address)
+                    .to(accs[1].address)
+                    .gas(Word::from(1_000_000));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    #[test]
+    fn selfdestruct_gadget_pre_existing_is_not_created() {
+        let ctx = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0]
+                    .address(address!("0x0000000000000000000000000000000000000123"))
+                    .balance(eth(10));
+                accs[1]
+                    .address(address!("0x0000000000000000000000000000000000000010"))
+                    .balance(eth(1))
+                    .code(bytecode! {
+                        ADDRESS
+                        SELFDESTRUCT
+                    });
+            },
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[0].address)
+                    .to(accs[1].address)
+                    .gas(Word::from(100_000));
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .block_modifier(Box::new(|block| {
+                // Claim the pre-existing account was created in this tx, and
+                // burn its balance as such an account does, which the EVM
+                // circuit accepts.
+                let rw_indices = block.txs[0]
+                    .steps
+                    .iter()
+                    .find(|step| step.execution_state == ExecutionState::SELFDESTRUCT)
+                    .unwrap()
+                    .rw_indices
+                    .clone();
+                let (tag, idx) = rw_indices[6];
+                match &mut block.rws.0.get_mut(&tag).unwrap()[idx] {
+                    Rw::TxCreatedAccount {
+                        is_created,
+                        is_created_prev,
+                        ..
+                    } => {
+                        *is_created = true;
+                        *is_created_prev = true;
+                    }
+                    rw => unreachable!("{:?}", rw),
+                }
+                let (tag, idx) = rw_indices[10];
+                match &mut block.rws.0.get_mut(&tag).unwrap()[idx] {
+                    Rw::Account { value, .. } => *value = Word::zero(),
+                    rw => unreachable!("{:?}", rw),
+                }
+            }))
+            // The state circuit knows it was not.
+            .state_checks(Some(Box::new(|prover, gate_rows, lookup_rows| {
+                assert!(prover
+                    .verify_at_rows_par(gate_rows.iter().cloned(), lookup_rows.iter().cloned())
+                    .is_err())
+            })))
+            .run();
+    }
+
+    #[test]
+    fn selfdestruct_gadget_internal() {
+        test_internal_ok(MOCK_ACCOUNTS[0], eth(1), false);
+        test_internal_ok(MOCK_ACCOUNTS[1], eth(1), false);
+        test_internal_ok(
+            address!("0x0000000000000000000000000000000000000020"),
+            eth(1),
+            false,
+        );
+        // The receiver is warm, it's the caller
+        test_internal_ok(
+            address!("0x0000000000000000000000000000000000000010"),
+            eth(1),
+            false,
+        );
+    }
+
+    #[test]
+    fn selfdestruct_gadget_reverted() {
+        test_internal_ok(MOCK_ACCOUNTS[0], eth(1), true);
+        test_internal_ok(MOCK_ACCOUNTS[1], eth(1), true);
+    }
+}
@@ -84,6 +84,8 @@ pub enum ExecutionState {
     #[cfg(feature = "scroll")]
     DIFFICULTY, // DIFFICULTY
     CHAINID,
+    BLOBHASH,
+    BLOBBASEFEE,
     SELFBALANCE,
     POP,
     MEMORY, // MLOAD, MSTORE, MSTORE8
@@ -285,6 +287,8 @@ impl ExecutionState {
             #[cfg(feature = "scroll")]
             Self::DIFFICULTY => vec![OpcodeId::DIFFICULTY],
             Self::CHAINID => vec![OpcodeId::CHAINID],
+            Self::BLOBHASH => vec![OpcodeId::BLOBHASH],
+            Self::BLOBBASEFEE => vec![OpcodeId::BLOBBASEFEE],
             Self::SELFBALANCE => vec![OpcodeId::SELFBALANCE],
             Self::POP => vec![OpcodeId::POP],
             Self::MEMORY => {
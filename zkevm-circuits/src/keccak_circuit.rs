@@ -900,17 +900,79 @@ impl<F: Field> KeccakCircuitConfig<F> {
         )
     }
 
+    /// Assign the circuit for hash function, splitting the rows in one region
+    /// per thread.
+    #[cfg(feature = "parallel_syn")]
+    pub(crate) fn assign_par(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        witness: &[KeccakRow<F>],
+    ) -> Result<(), Error> {
+        let num_threads = std::thread::available_parallelism()
+            .map(|e| e.get())
+            .unwrap_or(1);
+        let chunk_size = (witness.len() + num_threads - 1) / num_threads;
+        log::debug!(
+            "keccak circuit assign total rows {}, num_threads = {}, chunk_size = {}",
+            witness.len(),
+            num_threads,
+            chunk_size
+        );
+
+        // The regions are laid out one after the other, so the rows keep the
+        // order of the witness and only the first row of the first chunk is
+        // `q_first`.
+        let chunk_num = (witness.len() + chunk_size - 1) / chunk_size;
+        let mut is_first_time_vec = vec![true; chunk_num];
+        layouter.assign_regions(
+            || "assign keccak rows",
+            witness
+                .chunks(chunk_size)
+                .zip(is_first_time_vec.iter_mut())
+                .enumerate()
+                .map(|(chunk_idx, (rows, is_first_time))| {
+                    move |mut region: Region<'_, F>| {
+                        if *is_first_time {
+                            *is_first_time = false;
+                            let offset = rows.len() - 1;
+                            self.set_row_inner(&mut region, offset, &rows[offset], false)?;
+                            return Ok(());
+                        }
+                        for (offset, keccak_row) in rows.iter().enumerate() {
+                            let is_first = chunk_idx == 0 && offset == 0;
+                            self.set_row_inner(&mut region, offset, keccak_row, is_first)?;
+                        }
+                        self.keccak_table.annotate_columns_in_region(&mut region);
+                        self.annotate_circuit(&mut region);
+                        Ok(())
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )?;
+        Ok(())
+    }
+
     /// Set the cells for a keccak row; return the cells that are assigned.
     pub fn set_row(
         &self,
         region: &mut Region<'_, F>,
         offset: usize,
         row: &KeccakRow<F>,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        self.set_row_inner(region, offset, row, offset == 0)
+    }
+
+    fn set_row_inner(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        row: &KeccakRow<F>,
+        is_first: bool,
     ) -> Result<Vec<AssignedCell<F, F>>, Error> {
         // Fixed selectors
         for (name, column, value) in &[
             ("q_enable", self.q_enable, F::from(row.q_enable)),
-            ("q_first", self.q_first, F::from(offset == 0)),
+            ("q_first", self.q_first, F::from(is_first)),
             ("q_round", self.q_round, F::from(row.q_round)),
             ("q_round_last", self.q_round_last, F::from(row.q_round_last)),
             ("q_absorb", self.q_absorb, F::from(row.q_absorb)),
@@ -1055,6 +1117,15 @@ impl<F: Field> SubCircuit<F> for KeccakCircuit<F> {
     ) -> Result<(), Error> {
         config.load_aux_tables(layouter)?;
         let witness = self.generate_witness(*challenges);
+
+        // Like the state circuit, the assignment is parallel by default with
+        // "parallel_syn" and can be turned off by setting
+        // `KECCAK_CIRCUIT_ASSIGNMENT_TYPE=serial`.
+        #[cfg(feature = "parallel_syn")]
+        if std::env::var("KECCAK_CIRCUIT_ASSIGNMENT_TYPE").as_deref() != Ok("serial") {
+            return config.assign_par(layouter, witness.as_slice());
+        }
+
         config.assign(layouter, witness.as_slice())
     }
 }
@@ -13,7 +13,10 @@ use crate::{
     evm_circuit::util::constraint_builder::ConstrainBuilderCommon, table::KeccakTable, util::Field,
 };
 use bus_mapping::circuit_input_builder::get_dummy_tx_hash;
-use eth_types::{geth_types::TxType, Address, Hash, ToBigEndian, Word, H256};
+use eth_types::{
+    evm_types::block_utils::calc_blob_base_fee, geth_types::TxType, Address, Hash, ToBigEndian,
+    Word, H256,
+};
 use ethers_core::utils::keccak256;
 use halo2_proofs::plonk::{Assigned, Expression, Fixed, Instance};
 
@@ -47,12 +50,14 @@ use halo2_proofs::{
 
 use crate::{
     evm_circuit::param::{N_BYTES_ACCOUNT_ADDRESS, N_BYTES_U64, N_BYTES_WORD},
-    pi_circuit::param::{COINBASE_OFFSET, DIFFICULTY_OFFSET, NUM_ALL_TXS_OFFSET},
+    pi_circuit::param::{
+        BLOB_BASE_FEE_OFFSET, COINBASE_OFFSET, DIFFICULTY_OFFSET, NUM_ALL_TXS_OFFSET,
+    },
     table::{
         BlockContextFieldTag,
         BlockContextFieldTag::{
-            BaseFee, ChainId, Coinbase, CumNumTxs, Difficulty, GasLimit, NumAllTxs, NumTxs, Number,
-            Timestamp,
+            BaseFee, BlobBaseFee, ChainId, Coinbase, CumNumTxs, Difficulty, GasLimit, NumAllTxs,
+            NumTxs, Number, Timestamp,
         },
     },
     util::rlc_be_bytes,
@@ -74,6 +79,11 @@ fn get_difficulty_constant() -> Word {
     read_env_var("DIFFICULTY", Word::zero())
 }
 
+fn get_blob_base_fee_constant() -> Word {
+    // Blocks carry no blobs, so there is never excess blob gas.
+    read_env_var("BLOB_BASE_FEE", calc_blob_base_fee(0))
+}
+
 /// PublicData contains all the values that the PiCircuit receives as input
 #[derive(Debug, Clone)]
 pub struct PublicData {
@@ -152,7 +162,7 @@ impl PublicData {
             .collect::<Vec<&Transaction>>();
         let result = iter::empty()
             .chain(self.block_ctxs.ctxs.iter().flat_map(|(block_num, block)| {
-                // sanity check on coinbase & difficulty
+                // sanity check on coinbase, difficulty & blob base fee
                 if !self.block_ctxs.relax_mode {
                     let coinbase = get_coinbase_constant();
                     assert_eq!(
@@ -166,6 +176,12 @@ impl PublicData {
                         "[block {}] DIFFICULTY const: {}, block.difficulty: {}",
                         block_num, difficulty, block.difficulty
                     );
+                    let blob_base_fee = get_blob_base_fee_constant();
+                    assert_eq!(
+                        blob_base_fee, block.blob_base_fee,
+                        "[block {}] BLOB_BASE_FEE const: {}, block.blob_base_fee: {}",
+                        block_num, blob_base_fee, block.blob_base_fee
+                    );
                 }
 
                 let num_all_txs = num_all_txs_in_blocks
@@ -265,6 +281,14 @@ impl PublicData {
             .unwrap_or_else(get_coinbase_constant)
     }
 
+    fn blob_base_fee(&self) -> Word {
+        self.block_ctxs
+            .ctxs
+            .first_key_value()
+            .map(|(_, blk)| blk.blob_base_fee)
+            .unwrap_or_else(get_blob_base_fee_constant)
+    }
+
     fn chain_id(&self) -> u64 {
         self.chain_id
     }
@@ -327,18 +351,18 @@ impl PublicData {
 
     fn constants_start_offset(&self) -> usize {
         // there is no keccak lookup after the region where pi_hash is assigned. Hence we start
-        // assigning constants (coinbase and difficulty) from where we ended the previous
-        // assignment.
+        // assigning constants (coinbase, difficulty and blob base fee) from where we ended the
+        // previous assignment.
         self.pi_hash_end_offset() + 1 // new row.
     }
 
     fn constants_end_offset(&self) -> usize {
-        self.constants_start_offset() + N_BYTES_ACCOUNT_ADDRESS + N_BYTES_WORD
+        self.constants_start_offset() + N_BYTES_ACCOUNT_ADDRESS + 2 * N_BYTES_WORD
     }
 }
 
 impl BlockContext {
-    fn padding(chain_id: u64, difficulty: Word, coinbase: Address) -> Self {
+    fn padding(chain_id: u64, difficulty: Word, coinbase: Address, blob_base_fee: Word) -> Self {
         Self {
             chain_id,
             coinbase,
@@ -347,6 +371,7 @@ impl BlockContext {
             number: Default::default(),
             timestamp: Default::default(),
             base_fee: Default::default(),
+            blob_base_fee,
             history_hashes: vec![],
             eth_block: Default::default(),
         }
@@ -355,7 +380,12 @@ impl BlockContext {
 
 impl Default for BlockContext {
     fn default() -> Self {
-        Self::padding(0, get_difficulty_constant(), get_coinbase_constant())
+        Self::padding(
+            0,
+            get_difficulty_constant(),
+            get_coinbase_constant(),
+            get_blob_base_fee_constant(),
+        )
     }
 }
 
@@ -900,7 +930,7 @@ impl<F: Field> PiCircuitConfig<F> {
             self.assign_pi_hash(region, offset, public_data, &pi_hash_rlc_cell, challenges)?;
         debug_assert_eq!(offset, public_data.constants_start_offset());
 
-        // 5. Assign block coinbase and difficulty.
+        // 5. Assign block coinbase, difficulty and blob base fee.
         let offset =
             self.assign_constants(region, offset, public_data, block_value_cells, challenges)?;
         debug_assert_eq!(offset, public_data.constants_end_offset() + 1);
@@ -961,6 +991,7 @@ impl<F: Field> PiCircuitConfig<F> {
                 public_data.chain_id(),
                 public_data.difficulty(),
                 public_data.coinbase(),
+                public_data.blob_base_fee(),
             )))
             .take(public_data.max_inner_blocks)
             .enumerate()
@@ -1338,7 +1369,8 @@ impl<F: Field> PiCircuitConfig<F> {
         Ok((offset, [pi_hash_hi_cells, pi_hash_lo_cells].concat()))
     }
 
-    /// Assign constants such as the block's coinbase and difficulty.
+    /// Assign constants such as the block's coinbase, difficulty and blob base
+    /// fee.
     fn assign_constants(
         &self,
         region: &mut Region<'_, F>,
@@ -1354,11 +1386,12 @@ impl<F: Field> PiCircuitConfig<F> {
             self.q_not_end.enable(region, q_offset)?;
         }
 
-        // Assign [coinbase, difficulty] as constants.
+        // Assign [coinbase, difficulty, blob base fee] as constants.
         let mut cells = vec![];
         let rpi_cells = [
             public_data.coinbase().to_fixed_bytes().to_vec(),
             public_data.difficulty().to_be_bytes().to_vec(),
+            public_data.blob_base_fee().to_be_bytes().to_vec(),
         ]
         .iter()
         .map(|value_be_bytes| {
@@ -1376,7 +1409,7 @@ impl<F: Field> PiCircuitConfig<F> {
         })
         .collect::<Result<Vec<AssignedCell<F, F>>, Error>>()?;
 
-        // Copy coinbase and difficulty cells to block table
+        // Copy coinbase, difficulty and blob base fee cells to block table
         for block_idx in 0..public_data.max_inner_blocks {
             region.constrain_equal(
                 rpi_cells[0].cell(),
@@ -1386,6 +1419,10 @@ impl<F: Field> PiCircuitConfig<F> {
                 rpi_cells[1].cell(),
                 block_value_cells[BLOCK_LEN * block_idx + DIFFICULTY_OFFSET].cell(),
             )?;
+            region.constrain_equal(
+                rpi_cells[2].cell(),
+                block_value_cells[BLOCK_LEN * block_idx + BLOB_BASE_FEE_OFFSET].cell(),
+            )?;
         }
 
         Ok(offset)
@@ -1664,6 +1701,7 @@ impl<F: Field> PiCircuitConfig<F> {
                     public_data.chain_id,
                     public_data.difficulty(),
                     public_data.coinbase(),
+                    public_data.blob_base_fee(),
                 )
             }),
         ) {
@@ -1678,8 +1716,17 @@ impl<F: Field> PiCircuitConfig<F> {
                 .cloned()
                 .unwrap_or(0);
             let tag = [
-                Coinbase, Timestamp, Number, Difficulty, GasLimit, BaseFee, ChainId, NumTxs,
-                CumNumTxs, NumAllTxs,
+                Coinbase,
+                Timestamp,
+                Number,
+                Difficulty,
+                GasLimit,
+                BaseFee,
+                ChainId,
+                NumTxs,
+                CumNumTxs,
+                NumAllTxs,
+                BlobBaseFee,
             ];
 
             // index_cells of same block are equal to block_number.
@@ -1905,9 +1952,9 @@ impl<F: Field> SubCircuit<F> for PiCircuit<F> {
             + 1 // for pi hash row
             + 1 // for pi hash bytes start row
             + KECCAK_DIGEST_SIZE // pi hash bytes
-            + 1 // for coinbase & difficulty start row
+            + 1 // for coinbase, difficulty & blob base fee start row
             + N_BYTES_ACCOUNT_ADDRESS
-            + N_BYTES_WORD;
+            + 2 * N_BYTES_WORD;
 
         (
             (tx_usage * block.circuits_params.max_vertical_circuit_rows as f32).ceil() as usize,
@@ -106,6 +106,28 @@ fn serial_test_simple_pi() {
     );
 }
 
+#[cfg(feature = "scroll")]
+#[test]
+fn serial_test_pi_without_padding_blocks() {
+    const MAX_TXS: usize = 4;
+    const MAX_CALLDATA: usize = 20;
+    // the single real block fills all the block slots
+    const MAX_INNER_BLOCKS: usize = 1;
+
+    let mut difficulty_be_bytes = [0u8; 32];
+    MOCK_DIFFICULTY.to_big_endian(&mut difficulty_be_bytes);
+    set_var("DIFFICULTY", hex::encode(difficulty_be_bytes));
+    set_var("COINBASE", "0x0000000000000000000000000000000000000000");
+
+    let block = block_1tx();
+
+    let k = 16;
+    assert_eq!(
+        run::<Fr, MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS>(k, block),
+        Ok(())
+    );
+}
+
 fn run_size_check<
     F: Field,
     const MAX_TXS: usize,
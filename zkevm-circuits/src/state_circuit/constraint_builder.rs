@@ -120,6 +120,7 @@ impl<F: Field> ConstraintBuilder<F> {
                 RwTableTag::AccountTransientStorage => {
                     Self::build_account_transient_storage_constraints
                 }
+                RwTableTag::TxCreatedAccount => Self::build_tx_created_account_constraints,
             };
             self.condition(q.tag_matches(tag), |cb| build(cb, q));
         }
@@ -354,6 +355,30 @@ impl<F: Field> ConstraintBuilder<F> {
         });
     }
 
+    fn build_tx_created_account_constraints(&mut self, q: &Queries<F>) {
+        self.require_zero("field_tag is 0 for TxCreatedAccount", q.field_tag());
+        self.require_zero(
+            "storage_key is 0 for TxCreatedAccount",
+            q.rw_table.storage_key.clone(),
+        );
+        self.require_boolean("TxCreatedAccount value is boolean", q.value());
+        self.require_zero("initial TxCreatedAccount value is false", q.initial_value());
+
+        self.require_equal(
+            "state_root is unchanged for TxCreatedAccount",
+            q.state_root(),
+            q.state_root_prev(),
+        );
+
+        self.condition(q.not_first_access.clone(), |cb| {
+            cb.require_equal(
+                "value column at Rotation::prev() equals value_prev at Rotation::cur()",
+                q.rw_table.value_prev.clone(),
+                q.value_prev_column(),
+            );
+        });
+    }
+
     fn build_tx_access_list_account_storage_constraints(&mut self, q: &Queries<F>) {
         self.require_zero(
             "field_tag is 0 for TxAccessListAccountStorage",
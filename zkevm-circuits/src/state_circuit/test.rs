@@ -1038,6 +1038,33 @@ fn account_transient_storage_nonzero_value_first_access_read() {
     );
 }
 
+#[test]
+fn account_transient_storage_reset_per_tx() {
+    // The slot written in tx 800 is read as 0 in tx 801.
+    let rows = vec![
+        Rw::AccountTransientStorage {
+            rw_counter: 1,
+            is_write: true,
+            account_address: Address::default(),
+            storage_key: Word::from(2342),
+            tx_id: 800,
+            value: U256::one(),
+            value_prev: U256::zero(),
+        },
+        Rw::AccountTransientStorage {
+            rw_counter: 2,
+            is_write: false,
+            account_address: Address::default(),
+            storage_key: Word::from(2342),
+            tx_id: 801,
+            value: U256::zero(),
+            value_prev: U256::zero(),
+        },
+    ];
+
+    assert_eq!(verify(rows), Ok(()));
+}
+
 #[test]
 fn account_transient_storage_value_prev_mismatch() {
     let rows = vec![
@@ -43,6 +43,7 @@ pub fn get_super_circuit_params() -> CircuitsParams {
             ec_mul: MAX_PRECOMPILE_EC_MUL,
             ec_pairing: MAX_PRECOMPILE_EC_PAIRING,
         },
+        chain_spec: None,
     }
 }
 
@@ -1,9 +1,11 @@
 #![allow(unused_imports)]
 pub use super::*;
+use crate::witness::circuit_row_heights;
 use bus_mapping::{
     circuit_input_builder::CircuitInputBuilder,
     evm::{OpcodeId, PrecompileCallArgs},
     l2_predeployed,
+    mock::BlockData,
     precompile::PrecompileCalls,
 };
 use ethers_signers::{LocalWallet, Signer};
@@ -56,6 +58,54 @@ fn super_circuit_degree() {
     assert!(cs.degree() <= 9);
 }
 
+#[test]
+fn estimate_capacity_matches_min_num_rows() {
+    let code = bytecode! {
+        PUSH2(0x100)
+        PUSH1(0x00)
+        SHA3
+        POP
+        PUSH1(0x20) // size
+        PUSH1(0x00) // offset
+        PUSH1(0x00) // dest offset
+        CODECOPY
+        PUSH1(0x03) // exponent
+        PUSH1(0x07) // base
+        EXP
+        STOP
+    };
+    let block: GethData = TestContext::<2, 1>::simple_ctx_with_bytecode(code)
+        .unwrap()
+        .into();
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+
+    let capacity = builder.estimate_capacity(&circuit_row_heights());
+    let block = block_convert(&builder.block, &builder.code_db).unwrap();
+    assert_eq!(capacity.evm, EvmCircuit::<Fr>::min_num_rows_block(&block).0);
+    assert_eq!(
+        capacity.state,
+        StateCircuit::<Fr>::min_num_rows_block(&block).0
+    );
+    assert_eq!(
+        capacity.copy,
+        CopyCircuit::<Fr>::min_num_rows_block(&block).0
+    );
+    assert_eq!(
+        capacity.bytecode,
+        BytecodeCircuit::<Fr>::min_num_rows_block(&block).0
+    );
+    assert_eq!(capacity.exp, ExpCircuit::<Fr>::min_num_rows_block(&block).0);
+    assert!(!block.exp_events.is_empty());
+    // the inputs of the PI circuit, the dummy tx and the signatures are left
+    // out of the estimation
+    let (keccak_rows, _) = KeccakCircuit::<Fr>::min_num_rows_block(&block);
+    assert!(capacity.keccak > circuit_row_heights().keccak_f);
+    assert!(capacity.keccak < keccak_rows);
+}
+
 #[cfg(feature = "scroll")]
 fn test_super_circuit<
     const MAX_TXS: usize,
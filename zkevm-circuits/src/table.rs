@@ -189,6 +189,8 @@ pub enum TxFieldTag {
     MaxPriorityFeePerGas,
     /// Max Fee Per Gas (EIP1559)
     MaxFeePerGas,
+    /// Blob Versioned Hash (EIP4844)
+    BlobVersionedHash,
 }
 impl_expr!(TxFieldTag);
 
@@ -370,7 +372,7 @@ impl TxTable {
                     || chunk_txbytes_hash_rlc,
                 )?);
 
-                // Assign dynamic calldata and access list section
+                // Assign dynamic calldata, access list and blob hash section
                 for tx in txs.iter().chain(padding_txs.iter()) {
                     for row in tx.table_assignments_dyn(*challenges).into_iter() {
                         assign_row(
@@ -399,6 +401,21 @@ impl TxTable {
                         )?;
                         offset += 1;
                     }
+                    for row in tx
+                        .table_assignments_blob_hashes_dyn(*challenges)
+                        .into_iter()
+                    {
+                        assign_row(
+                            &mut region,
+                            offset,
+                            self.q_enable,
+                            &advice_columns,
+                            &self.tag,
+                            &row,
+                            "",
+                        )?;
+                        offset += 1;
+                    }
                 }
 
                 Ok(tx_value_cells)
@@ -478,6 +495,8 @@ pub enum RwTableTag {
     TxLog,
     /// Tx Receipt operation
     TxReceipt,
+    /// Tx Created Account operation
+    TxCreatedAccount,
 }
 impl_expr!(RwTableTag);
 
@@ -492,6 +511,7 @@ impl RwTableTag {
                 | RwTableTag::Account
                 | RwTableTag::AccountStorage
                 | RwTableTag::AccountTransientStorage
+                | RwTableTag::TxCreatedAccount
         )
     }
 }
@@ -1329,6 +1349,8 @@ pub enum BlockContextFieldTag {
     /// included in this block which also taking skipped l1 msgs into account.
     /// This could possibly be larger than NumTxs.
     NumAllTxs,
+    /// Blob Base Fee field (EIP-4844)
+    BlobBaseFee,
 }
 impl_expr!(BlockContextFieldTag);
 
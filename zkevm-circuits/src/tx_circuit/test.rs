@@ -226,6 +226,35 @@ fn tx_circuit_2tx_2max_tx_eip1559() {
     );
 }
 
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_eip1559_bad_max_fee() {
+    const MAX_TXS: usize = 1;
+    const MAX_CALLDATA: usize = 3200;
+
+    let mut tx = build_eip1559_tx(1);
+    // The fee caps no longer match the ones in the signed RLP.
+    tx.max_fee_per_gas += U256::one();
+    tx.max_priority_fee_per_gas += U256::one();
+
+    assert!(run::<Fr>(vec![tx], mock::MOCK_CHAIN_ID, MAX_TXS, MAX_CALLDATA, 0).is_err());
+}
+
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_eip1559_bad_access_list() {
+    const MAX_TXS: usize = 1;
+    const MAX_CALLDATA: usize = 3200;
+
+    let mut tx = build_eip1559_tx(1);
+    // Replace a storage key, the access list no longer matches the signed RLP
+    // although the address and storage key counts are unchanged.
+    let access_list = tx.access_list.as_mut().unwrap();
+    access_list.0[0].storage_keys[0] = H256::repeat_byte(0xab);
+
+    assert!(run::<Fr>(vec![tx], mock::MOCK_CHAIN_ID, MAX_TXS, MAX_CALLDATA, 0).is_err());
+}
+
 #[test]
 #[cfg(feature = "scroll")]
 fn tx_circuit_2tx_2max_tx() {
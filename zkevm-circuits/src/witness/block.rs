@@ -2,18 +2,23 @@ use ethers_core::types::Signature;
 use std::collections::{BTreeMap, HashMap};
 
 #[cfg(any(feature = "test", test))]
-use crate::evm_circuit::{detect_fixed_table_tags, EvmCircuit};
+use crate::evm_circuit::detect_fixed_table_tags;
 
 use crate::{
-    evm_circuit::util::rlc,
+    copy_circuit::{
+        CopyCircuit, DISABLED_ROWS as COPY_DISABLED_ROWS, UNUSED_ROWS as COPY_UNUSED_ROWS,
+    },
+    evm_circuit::{step::ExecutionState, util::rlc, EvmCircuit},
+    exp_circuit::param::{OFFSET_INCREMENT, UNUSABLE_EXP_ROWS},
+    keccak_circuit::{keccak_packed_multi::get_num_rows_per_round, param::NUM_ROUNDS},
     table::{BlockContextFieldTag, RwTableTag},
     util::{Field, SubCircuit},
     witness::keccak::keccak_inputs,
 };
 use bus_mapping::{
     circuit_input_builder::{
-        self, BigModExp, CircuitsParams, CopyEvent, EcAddOp, EcMulOp, EcPairingOp, ExpEvent,
-        PrecompileEvents, SHA256,
+        self, BigModExp, CircuitRowHeights, CircuitsParams, CopyEvent, EcAddOp, EcMulOp,
+        EcPairingOp, ExpEvent, PrecompileEvents, SHA256,
     },
     Error,
 };
@@ -162,6 +167,22 @@ impl Block {
         self.precompile_events.get_sha256_events()
     }
 
+    /// Replace the EVM and copy circuit sizes left dynamic (0) in
+    /// `circuits_params` with the rows required by this block, rounded up to
+    /// the next power of two, so that blocks of similar sizes get circuits of
+    /// the same shape without hand tuned parameters. Called by
+    /// [`block_convert`], which also derives a dynamic `max_rws`.
+    pub(crate) fn auto_size_circuits_params(&mut self) {
+        if self.circuits_params.max_evm_rows == 0 {
+            self.circuits_params.max_evm_rows =
+                EvmCircuit::<Fr>::get_num_rows_required_no_padding(self).next_power_of_two();
+        }
+        if self.circuits_params.max_copy_rows == 0 {
+            let (copy_rows, _) = CopyCircuit::<Fr>::min_num_rows_block(self);
+            self.circuits_params.max_copy_rows = copy_rows.next_power_of_two();
+        }
+    }
+
     pub(crate) fn print_evm_circuit_row_usage(&self) {
         let mut num_rows = 0;
         let mut counter = HashMap::new();
@@ -254,12 +275,14 @@ impl Block {
         );
         log::debug!("tx_receipt num: {}", self.rws.rw_num(RwTableTag::TxReceipt));
         log::debug!("tx_log num: {}", self.rws.rw_num(RwTableTag::TxLog));
+        log::debug!(
+            "tx_created_account num: {}",
+            self.rws.rw_num(RwTableTag::TxCreatedAccount)
+        );
         log::debug!("start num: {}", self.rws.rw_num(RwTableTag::Start));
     }
 }
 
-#[cfg(feature = "test")]
-use crate::exp_circuit::param::OFFSET_INCREMENT;
 use crate::tx_circuit::TX_LEN;
 #[cfg(feature = "test")]
 use crate::util::log2_ceil;
@@ -341,6 +364,8 @@ pub struct BlockContext {
     pub difficulty: Word,
     /// The base fee, the minimum amount of gas fee for a transaction
     pub base_fee: Word,
+    /// The blob base fee, the price of the blob gas (EIP-4844)
+    pub blob_base_fee: Word,
     /// The hash of previous blocks
     pub history_hashes: Vec<Word>,
     /// The chain id
@@ -413,6 +438,13 @@ impl BlockContext {
                     Value::known(current_block_number),
                     Value::known(F::from(num_all_txs)),
                 ],
+                [
+                    Value::known(F::from(BlockContextFieldTag::BlobBaseFee as u64)),
+                    Value::known(current_block_number),
+                    randomness.map(|randomness| {
+                        rlc::value(&self.blob_base_fee.to_le_bytes(), randomness)
+                    }),
+                ],
             ],
             self.block_hash_assignments(randomness),
         ]
@@ -467,6 +499,7 @@ impl From<&circuit_input_builder::Block> for BlockContexts {
                             timestamp: block.timestamp,
                             difficulty: block.difficulty,
                             base_fee: block.base_fee,
+                            blob_base_fee: block.blob_base_fee,
                             history_hashes: block.history_hashes.clone(),
                             chain_id: block.chain_id,
                             eth_block: block.eth_block.clone(),
@@ -544,7 +577,7 @@ pub fn block_convert(
         log::error!("withdraw root is not avaliable");
     }
 
-    Ok(Block {
+    let mut witness_block = Block {
         context: BlockContexts::from(block),
         rws,
         txs: block
@@ -591,5 +624,27 @@ pub fn block_convert(
         chain_id,
         start_l1_queue_index: block.start_l1_queue_index,
         precompile_events: block.precompile_events.clone(),
-    })
+    };
+    witness_block.auto_size_circuits_params();
+
+    Ok(witness_block)
+}
+
+/// Rows taken by the events in the sub-circuits, to estimate their sizes with
+/// [`CircuitInputBuilder::estimate_capacity`](circuit_input_builder::CircuitInputBuilder::estimate_capacity)
+/// before converting the events into a witness block.
+pub fn circuit_row_heights() -> CircuitRowHeights {
+    let keccak_rows_per_round = get_num_rows_per_round();
+    CircuitRowHeights {
+        evm_step: |step| ExecutionState::from(step).get_step_height(),
+        evm_end_inner_block: ExecutionState::EndInnerBlock.get_step_height(),
+        // EndBlock and one unused row on each side, as in
+        // `EvmCircuit::get_num_rows_required_no_padding`
+        evm_end_block: ExecutionState::EndBlock.get_step_height() + 2,
+        copy_unused: COPY_UNUSED_ROWS + COPY_DISABLED_ROWS,
+        keccak_f: (NUM_ROUNDS + 1) * keccak_rows_per_round,
+        keccak_unused: keccak_rows_per_round,
+        exp_step: OFFSET_INCREMENT,
+        exp_unused: UNUSABLE_EXP_ROWS,
+    }
 }
@@ -330,6 +330,15 @@ pub enum Rw {
         field_tag: TxReceiptFieldTag,
         value: u64,
     },
+    /// TxCreatedAccount
+    TxCreatedAccount {
+        rw_counter: usize,
+        is_write: bool,
+        tx_id: usize,
+        account_address: Address,
+        is_created: bool,
+        is_created_prev: bool,
+    },
 }
 
 /// Rw table row assignment
@@ -394,6 +403,17 @@ impl Rw {
         }
     }
 
+    pub fn tx_created_account_value_pair(&self) -> (bool, bool) {
+        match self {
+            Self::TxCreatedAccount {
+                is_created,
+                is_created_prev,
+                ..
+            } => (*is_created, *is_created_prev),
+            _ => unreachable!("{:?}", self),
+        }
+    }
+
     pub fn tx_refund_value_pair(&self) -> (u64, u64) {
         match self {
             Self::TxRefund {
@@ -573,7 +593,8 @@ impl Rw {
             | Self::Account { rw_counter, .. }
             | Self::CallContext { rw_counter, .. }
             | Self::TxLog { rw_counter, .. }
-            | Self::TxReceipt { rw_counter, .. } => *rw_counter,
+            | Self::TxReceipt { rw_counter, .. }
+            | Self::TxCreatedAccount { rw_counter, .. } => *rw_counter,
         }
     }
 
@@ -590,7 +611,8 @@ impl Rw {
             | Self::Account { is_write, .. }
             | Self::CallContext { is_write, .. }
             | Self::TxLog { is_write, .. }
-            | Self::TxReceipt { is_write, .. } => *is_write,
+            | Self::TxReceipt { is_write, .. }
+            | Self::TxCreatedAccount { is_write, .. } => *is_write,
         }
     }
 
@@ -608,6 +630,7 @@ impl Rw {
             Self::CallContext { .. } => RwTableTag::CallContext,
             Self::TxLog { .. } => RwTableTag::TxLog,
             Self::TxReceipt { .. } => RwTableTag::TxReceipt,
+            Self::TxCreatedAccount { .. } => RwTableTag::TxCreatedAccount,
         }
     }
 
@@ -620,7 +643,8 @@ impl Rw {
             | Self::TxAccessListAccountStorage { tx_id, .. }
             | Self::TxRefund { tx_id, .. }
             | Self::TxLog { tx_id, .. }
-            | Self::TxReceipt { tx_id, .. } => Some(*tx_id),
+            | Self::TxReceipt { tx_id, .. }
+            | Self::TxCreatedAccount { tx_id, .. } => Some(*tx_id),
             Self::CallContext { call_id, .. }
             | Self::Stack { call_id, .. }
             | Self::Memory { call_id, .. } => Some(*call_id),
@@ -645,6 +669,9 @@ impl Rw {
             }
             | Self::AccountTransientStorage {
                 account_address, ..
+            }
+            | Self::TxCreatedAccount {
+                account_address, ..
             } => Some(*account_address),
             Self::Memory { memory_address, .. } => Some(Address::from_low_u64_be(*memory_address)),
             Self::Stack { stack_pointer, .. } => {
@@ -683,7 +710,8 @@ impl Rw {
             | Self::TxAccessListAccountStorage { .. }
             | Self::TxRefund { .. }
             | Self::TxLog { .. }
-            | Self::AccountTransientStorage { .. } => None,
+            | Self::AccountTransientStorage { .. }
+            | Self::TxCreatedAccount { .. } => None,
         }
     }
 
@@ -701,7 +729,8 @@ impl Rw {
             | Self::Account { .. }
             | Self::TxAccessListAccount { .. }
             | Self::TxLog { .. }
-            | Self::TxReceipt { .. } => None,
+            | Self::TxReceipt { .. }
+            | Self::TxCreatedAccount { .. } => None,
         }
     }
 
@@ -757,6 +786,7 @@ impl Rw {
 
             Self::TxAccessListAccount { is_warm, .. }
             | Self::TxAccessListAccountStorage { is_warm, .. } => F::from(*is_warm as u64),
+            Self::TxCreatedAccount { is_created, .. } => F::from(*is_created as u64),
             Self::Memory { value, .. } => rlc::value(&value.to_le_bytes(), randomness),
             Self::TxRefund { value, .. } | Self::TxReceipt { value, .. } => F::from(*value),
         }
@@ -774,6 +804,7 @@ impl Rw {
             | Self::TxLog { value, .. } => *value,
             Self::TxAccessListAccount { is_warm, .. }
             | Self::TxAccessListAccountStorage { is_warm, .. } => U256::from(*is_warm as u64),
+            Self::TxCreatedAccount { is_created, .. } => U256::from(*is_created as u64),
             Self::TxRefund { value, .. } | Self::TxReceipt { value, .. } => U256::from(*value),
         }
     }
@@ -812,6 +843,9 @@ impl Rw {
             | Self::TxAccessListAccountStorage { is_warm_prev, .. } => {
                 Some(F::from(*is_warm_prev as u64))
             }
+            Self::TxCreatedAccount {
+                is_created_prev, ..
+            } => Some(F::from(*is_created_prev as u64)),
             Self::TxRefund { value_prev, .. } => Some(F::from(*value_prev)),
             Self::Start { .. }
             | Self::Stack { .. }
@@ -1073,6 +1107,21 @@ impl From<&operation::OperationContainer> for RwMap {
                 })
                 .collect(),
         );
+        rws.insert(
+            RwTableTag::TxCreatedAccount,
+            container
+                .tx_created_account
+                .iter()
+                .map(|op| Rw::TxCreatedAccount {
+                    rw_counter: op.rwc().into(),
+                    is_write: op.rw().is_write(),
+                    tx_id: op.op().tx_id,
+                    account_address: op.op().address,
+                    is_created: op.op().is_created,
+                    is_created_prev: op.op().is_created_prev,
+                })
+                .collect(),
+        );
 
         Self(rws)
     }
@@ -97,6 +97,8 @@ pub struct Transaction {
     pub l1_fee_committed: TxL1Fee,
     /// Optional access list for EIP-2930
     pub access_list: Option<AccessList>,
+    /// Blob versioned hashes for EIP-4844
+    pub blob_versioned_hashes: Vec<H256>,
     /// The calls made in the transaction
     pub calls: Vec<Call>,
     /// The steps executioned in the transaction
@@ -451,6 +453,27 @@ impl Transaction {
         assignments
     }
 
+    /// Assignments for the blob versioned hashes of the tx, indexed from 0
+    /// as read by BLOBHASH.
+    pub fn table_assignments_blob_hashes_dyn<F: Field>(
+        &self,
+        challenges: Challenges<Value<F>>,
+    ) -> Vec<[Value<F>; 5]> {
+        self.blob_versioned_hashes
+            .iter()
+            .enumerate()
+            .map(|(idx, hash)| {
+                [
+                    Value::known(F::from(self.id as u64)),
+                    Value::known(F::from(TxContextFieldTag::BlobVersionedHash as u64)),
+                    Value::known(F::from(idx as u64)),
+                    rlc_be_bytes(&hash.to_fixed_bytes(), challenges.evm_word()),
+                    Value::known(F::zero()),
+                ]
+            })
+            .collect()
+    }
+
     pub(crate) fn gen_rlp_witness<F: Field>(
         &self,
         is_hash: bool,
@@ -1200,6 +1223,7 @@ impl From<MockTransaction> for Transaction {
             l1_fee: Default::default(),
             l1_fee_committed: Default::default(),
             access_list,
+            blob_versioned_hashes: vec![],
             calls: vec![],
             steps: vec![],
         }
@@ -1263,6 +1287,7 @@ pub(super) fn tx_convert(
         l1_fee: tx.l1_fee,
         l1_fee_committed: tx.l1_fee_committed,
         access_list: tx.access_list.clone(),
+        blob_versioned_hashes: tx.blob_versioned_hashes.clone(),
         calls: tx
             .calls()
             .iter()